@@ -36,6 +36,26 @@ pub struct StatusSprites {
     #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 6, rows = 1))]
     oiled_layout: Handle<TextureAtlasLayout>,
 
+    #[asset(path = "images/statuseffects/stunned.png")]
+    stunned_sprite: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 6, rows = 1))]
+    stunned_layout: Handle<TextureAtlasLayout>,
+
+    #[asset(path = "images/statuseffects/poisoned.png")]
+    poisoned_sprite: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 6, rows = 1))]
+    poisoned_layout: Handle<TextureAtlasLayout>,
+
+    #[asset(path = "images/statuseffects/magnetized.png")]
+    magnetized_sprite: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 6, rows = 1))]
+    magnetized_layout: Handle<TextureAtlasLayout>,
+
+    #[asset(path = "images/statuseffects/slippery.png")]
+    slippery_sprite: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 6, rows = 1))]
+    slippery_layout: Handle<TextureAtlasLayout>,
+
     #[asset(path = "images/statuseffects/pushed.png")]
     pushed_sprite: Handle<Image>,
     #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 1, rows = 1))]
@@ -61,6 +81,10 @@ impl StatusSprites {
             StatusEnum::Oiled => (&self.oiled_sprite, &self.oiled_layout),
             StatusEnum::Ignited => (&self.burning_sprite, &self.burning_layout),
             StatusEnum::Chilled => (&self.frozen_sprite, &self.frozen_layout),
+            StatusEnum::Stunned => (&self.stunned_sprite, &self.stunned_layout),
+            StatusEnum::Poisoned => (&self.poisoned_sprite, &self.poisoned_layout),
+            StatusEnum::Magnetized => (&self.magnetized_sprite, &self.magnetized_layout),
+            StatusEnum::Slippery => (&self.slippery_sprite, &self.slippery_layout),
         }
     }
 
@@ -74,6 +98,10 @@ impl StatusSprites {
             StatusEnum::Oiled => &[0, 1, 2, 3, 4, 5],
             StatusEnum::Ignited => &[0, 1, 2, 3, 4, 5],
             StatusEnum::Chilled => &[0, 1, 2, 3, 4, 5],
+            StatusEnum::Stunned => &[0, 1, 2, 3, 4, 5],
+            StatusEnum::Poisoned => &[0, 1, 2, 3, 4, 5],
+            StatusEnum::Magnetized => &[0, 1, 2],
+            StatusEnum::Slippery => &[0, 1, 2, 3, 4, 5],
         }
     }
 
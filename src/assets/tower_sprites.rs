@@ -1,4 +1,4 @@
-use crate::data::Tower;
+use crate::data::{Tower, TowerArt};
 use crate::gameplay::animation::AnimationFrameQueue;
 use crate::level::components::LEVEL_SCALING;
 use crate::level::resource::CellDirection;
@@ -65,38 +65,42 @@ pub struct TowerSprites {
 
 impl TowerSprites {
     pub fn tower_sprite(&self, tower: &Tower) -> (&Handle<Image>, &Handle<TextureAtlasLayout>) {
-        match tower {
-            Tower::Piston => (&self.piston_sprite, &self.piston_layout),
-            Tower::Fan => (&self.fan_sprite, &self.fan_layout),
-            Tower::SpikePit => (&self.spike_sprite, &self.spike_layout),
-            Tower::Oil => (&self.oil_sprite, &self.oil_layout),
-            Tower::TrapDoor => (&self.trap_door_sprite, &self.trap_door_layout),
-            Tower::Tesla => (&self.tesla_sprite, &self.tesla_layout),
-            Tower::Water => (&self.water_sprite, &self.water_layout),
-            Tower::Acid => (&self.acid_sprite, &self.acid_layout),
-            Tower::Flame => (&self.flame_sprite, &self.flame_layout),
-            Tower::Portal => (&self.portal_sprite, &self.portal_layout),
-            Tower::Ice => (&self.ice_sprite, &self.ice_layout),
+        match tower.sprite_tower() {
+            TowerArt::Piston => (&self.piston_sprite, &self.piston_layout),
+            TowerArt::Fan => (&self.fan_sprite, &self.fan_layout),
+            TowerArt::SpikePit => (&self.spike_sprite, &self.spike_layout),
+            TowerArt::Oil => (&self.oil_sprite, &self.oil_layout),
+            TowerArt::TrapDoor => (&self.trap_door_sprite, &self.trap_door_layout),
+            TowerArt::Tesla => (&self.tesla_sprite, &self.tesla_layout),
+            TowerArt::Water => (&self.water_sprite, &self.water_layout),
+            TowerArt::Acid => (&self.acid_sprite, &self.acid_layout),
+            TowerArt::Flame => (&self.flame_sprite, &self.flame_layout),
+            TowerArt::Portal => (&self.portal_sprite, &self.portal_layout),
+            TowerArt::Ice => (&self.ice_sprite, &self.ice_layout),
         }
     }
 
-    pub fn tower_bundle(&self, tower: &Tower, direction: &CellDirection) -> impl Bundle {
+    pub fn tower_bundle(
+        &self,
+        tower: &Tower,
+        direction: &CellDirection,
+    ) -> (Sprite, AnimationFrameQueue) {
         let (image, atlas) = self.tower_sprite(tower);
 
         let idle_frames = direction.idle_frames(tower);
         let mut animation_controller = AnimationFrameQueue::new(idle_frames);
 
         if [
-            Tower::Tesla,
-            Tower::Piston,
-            Tower::Water,
-            Tower::Flame,
-            Tower::Acid,
-            Tower::Ice,
-            Tower::Oil,
-            Tower::Fan,
+            TowerArt::Tesla,
+            TowerArt::Piston,
+            TowerArt::Water,
+            TowerArt::Flame,
+            TowerArt::Acid,
+            TowerArt::Ice,
+            TowerArt::Oil,
+            TowerArt::Fan,
         ]
-        .contains(tower)
+        .contains(&tower.sprite_tower())
         {
             animation_controller.set_override(direction.attack_frames(tower));
         }
@@ -104,6 +108,7 @@ impl TowerSprites {
         (
             Sprite {
                 image: image.clone(),
+                color: tower.tint(),
                 custom_size: Some(Vec2::splat(LEVEL_SCALING)),
                 texture_atlas: Some(TextureAtlas {
                     index: idle_frames[0],
@@ -118,63 +123,63 @@ impl TowerSprites {
 
 impl CellDirection {
     pub fn idle_frames(&self, tower: &Tower) -> &'static [usize] {
-        match tower {
-            Tower::Piston => match self {
+        match tower.sprite_tower() {
+            TowerArt::Piston => match self {
                 CellDirection::Down => &[4],
                 CellDirection::Up => &[9],
                 CellDirection::Left => &[14],
                 CellDirection::Right => &[14],
             },
-            Tower::Fan => match self {
+            TowerArt::Fan => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[4],
                 CellDirection::Left => &[8],
                 CellDirection::Right => &[8],
             },
-            Tower::SpikePit => match self {
+            TowerArt::SpikePit => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[1],
                 CellDirection::Left => &[2],
                 CellDirection::Right => &[2],
             },
-            Tower::Oil => match self {
+            TowerArt::Oil => match self {
                 CellDirection::Down => &[0, 1, 2, 3, 4, 5, 6],
                 CellDirection::Up => &[14, 15, 16, 17, 18, 19, 20, 21, 22],
                 CellDirection::Left => &[33],
                 CellDirection::Right => &[33],
             },
-            Tower::TrapDoor => match self {
+            TowerArt::TrapDoor => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[5],
                 CellDirection::Left => &[5],
                 CellDirection::Right => &[5],
             },
-            Tower::Tesla => match self {
+            TowerArt::Tesla => match self {
                 CellDirection::Down => &[0, 1, 2, 3, 4],
                 CellDirection::Up => &[10, 11, 12, 13, 14],
                 CellDirection::Left => &[20, 21, 22, 23, 24],
                 CellDirection::Right => &[20, 21, 22, 23, 24],
             },
-            Tower::Water => match self {
+            TowerArt::Water => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[12],
                 CellDirection::Left => &[24],
                 CellDirection::Right => &[24],
             },
-            Tower::Acid => match self {
+            TowerArt::Acid => match self {
                 CellDirection::Down => &[0, 1, 2, 3, 4, 5, 6],
                 CellDirection::Up => &[14, 15, 16, 17, 18, 19, 20, 21, 22],
                 CellDirection::Left => &[33],
                 CellDirection::Right => &[33],
             },
-            Tower::Flame => match self {
+            TowerArt::Flame => match self {
                 CellDirection::Down => &[0, 1, 2, 3, 4, 5, 6],
                 CellDirection::Up => &[14, 15, 16, 17, 18, 19, 20, 21, 22],
                 CellDirection::Left => &[33],
                 CellDirection::Right => &[33],
             },
-            Tower::Portal => &[0],
-            Tower::Ice => match self {
+            TowerArt::Portal => &[0],
+            TowerArt::Ice => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[7],
                 CellDirection::Left => &[14],
@@ -184,68 +189,68 @@ impl CellDirection {
     }
 
     pub fn attack_frames(&self, tower: &Tower) -> &'static [usize] {
-        match tower {
-            Tower::Tesla => match self {
+        match tower.sprite_tower() {
+            TowerArt::Tesla => match self {
                 CellDirection::Down => &[5, 6, 7, 8],
                 CellDirection::Up => &[15, 16, 17, 18],
                 CellDirection::Left => &[25, 26, 27, 28],
                 CellDirection::Right => &[25, 26, 27, 28],
             },
-            Tower::Water => match self {
+            TowerArt::Water => match self {
                 CellDirection::Down => &[6, 7, 8, 9, 10],
                 CellDirection::Up => &[18, 19, 20, 21, 22],
                 CellDirection::Left => &[30, 31, 32, 33, 34],
                 CellDirection::Right => &[30, 31, 32, 33, 34],
             },
-            Tower::Ice => match self {
+            TowerArt::Ice => match self {
                 CellDirection::Down => &[1, 2, 3, 4, 5, 6],
                 CellDirection::Up => &[8, 9, 10, 11, 12, 13],
                 CellDirection::Left => &[15, 16, 17, 18, 19, 20],
                 CellDirection::Right => &[15, 16, 17, 18, 19, 20],
             },
-            Tower::Flame => match self {
+            TowerArt::Flame => match self {
                 CellDirection::Down => &[7, 8, 9, 10, 11, 12, 13],
                 CellDirection::Up => &[23, 24, 25, 26, 27, 28, 29, 30, 31, 32],
                 CellDirection::Left => &[33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44],
                 CellDirection::Right => &[33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44],
             },
-            Tower::Oil => match self {
+            TowerArt::Oil => match self {
                 CellDirection::Down => &[7, 8, 9, 10, 11, 12, 13],
                 CellDirection::Up => &[14, 15, 16, 17, 18, 19, 20, 21, 22],
                 CellDirection::Left => &[33, 34, 35, 36],
                 CellDirection::Right => &[33, 34, 35, 36],
             },
-            Tower::Fan => match self {
+            TowerArt::Fan => match self {
                 CellDirection::Down => &[1, 2, 3],
                 CellDirection::Up => &[5, 6, 7],
                 CellDirection::Left => &[9, 10, 11],
                 CellDirection::Right => &[9, 10, 11],
             },
-            Tower::Acid => match self {
+            TowerArt::Acid => match self {
                 CellDirection::Down => &[7, 8, 9, 10, 11, 12, 13],
                 CellDirection::Up => &[14, 15, 16, 17, 18, 19, 20, 21, 22],
                 CellDirection::Left => &[33, 34, 35, 36, 33],
                 CellDirection::Right => &[33, 34, 35, 36, 33],
             },
-            Tower::TrapDoor => match self {
+            TowerArt::TrapDoor => match self {
                 CellDirection::Down => &[1, 2, 3, 4, 4, 3, 2, 1],
                 CellDirection::Up => &[5],
                 CellDirection::Left => &[5],
                 CellDirection::Right => &[5],
             },
-            Tower::SpikePit => match self {
+            TowerArt::SpikePit => match self {
                 CellDirection::Down => &[0],
                 CellDirection::Up => &[1],
                 CellDirection::Left => &[2],
                 CellDirection::Right => &[2],
             },
-            Tower::Piston => match self {
+            TowerArt::Piston => match self {
                 CellDirection::Down => &[0, 1, 2, 3],
                 CellDirection::Up => &[5, 6, 7, 8],
                 CellDirection::Left => &[10, 11, 12, 13],
                 CellDirection::Right => &[10, 11, 12, 13],
             },
-            _ => todo!(),
+            TowerArt::Portal => &[0],
         }
     }
 }
@@ -1,8 +1,17 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
-use crate::gameplay::wave_manager::Wave;
-use crate::prefabs::enemies::{basic_trooper, chonkus_trooper, turbo_trooper};
+use crate::data::{
+    PlayerState, Tower,
+    tutorials::{self, TutorialStep},
+};
+use crate::gameplay::{blueprints::BlueprintTower, wave_manager::Wave};
+use crate::input::Action;
+use crate::level::components::{AdjacentId, ExactPosition, GeneralPosition, WallDirection};
+use crate::prefabs::enemies::{
+    basic_trooper, chonkus_trooper, emp_trooper, saboteur_trooper, soaker_trooper, turbo_trooper,
+};
 
 pub const MAP_TEXT: &'static str = ">>>
 ";
@@ -48,10 +57,191 @@ x^v>>^v
 >>>^>^>
 ";
 
+pub const MAP_TEXT8: &'static str = ">>>>>^
+^<<<<<
+>>>>>^
+^<<<<<
+>>>>>^
+";
+
 #[derive(Resource, Clone)]
 pub struct LevelData {
     pub maps: Vec<&'static str>,
     pub enemies: Vec<VecDeque<Wave>>,
+    pub economies: Vec<EconomyConfig>,
+    pub info: Vec<LevelInfo>,
+    pub setups: Vec<LevelSetup>,
+    // Steps walked through the first time a level is played, empty for none
+    pub tutorials: Vec<Vec<TutorialStep>>,
+}
+
+impl LevelData {
+    pub fn setup(&self, level: usize) -> LevelSetup {
+        self.setups.get(level).cloned().unwrap_or_default()
+    }
+
+    pub fn tutorial(&self, level: usize) -> &[TutorialStep] {
+        self.tutorials.get(level).map_or(&[], |w| w.as_slice())
+    }
+}
+
+// What the player has to work with when a level starts
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelSetup {
+    pub towers: Vec<Tower>,
+    pub money: i32,
+    pub lives: i32,
+    pub preplaced: Vec<PreplacedTower>,
+}
+
+impl Default for LevelSetup {
+    fn default() -> Self {
+        let player_state = PlayerState::default();
+        Self {
+            // Only as many towers as the hotbar has hotkeys for
+            towers: Tower::all()
+                .into_iter()
+                .take(Action::HOTBAR.len())
+                .collect(),
+            money: player_state.money,
+            lives: player_state.max_health,
+            preplaced: Vec::new(),
+        }
+    }
+}
+
+impl LevelSetup {
+    pub fn player_state(&self) -> PlayerState {
+        PlayerState {
+            money: self.money,
+            health: self.lives,
+            max_health: self.lives,
+        }
+    }
+
+    pub fn allows(&self, tower: Tower) -> bool {
+        self.towers.contains(&tower)
+    }
+}
+
+// A free tower the level starts with, locked ones can't be sold
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreplacedTower {
+    pub placement: BlueprintTower,
+    pub locked: bool,
+}
+
+impl PreplacedTower {
+    // On the floor of cell (x, y)
+    pub fn floor(tower: Tower, x: usize, y: usize) -> Self {
+        Self::new(
+            tower,
+            AdjacentId {
+                unit_x: x,
+                unit_y: y,
+                general_position: GeneralPosition::UpDown,
+            },
+            ExactPosition::Floor,
+        )
+    }
+
+    // On the left or right wall of cell (x, y)
+    pub fn wall(tower: Tower, x: usize, y: usize, side: WallDirection) -> Self {
+        let (unit_x, exact_position) = match side {
+            WallDirection::Left => (x, ExactPosition::Wall(WallDirection::Left)),
+            WallDirection::Right => (x + 1, ExactPosition::Wall(WallDirection::Right)),
+        };
+        Self::new(
+            tower,
+            AdjacentId {
+                unit_x,
+                unit_y: y,
+                general_position: GeneralPosition::LeftRight,
+            },
+            exact_position,
+        )
+    }
+
+    fn new(tower: Tower, piece: AdjacentId, exact_position: ExactPosition) -> Self {
+        Self {
+            placement: BlueprintTower {
+                tower,
+                piece,
+                exact_position,
+                orientation: exact_position.mount_direction(),
+                fan_direction: None,
+                upgrade_level: 0,
+            },
+            locked: false,
+        }
+    }
+
+    pub fn locked(mut self) -> Self {
+        self.locked = true;
+        self
+    }
+}
+
+// Shown on the level selector
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl LevelInfo {
+    pub const fn new(name: &'static str, description: &'static str) -> Self {
+        Self { name, description }
+    }
+}
+
+// Each level's economy lives in its own file. They are compiled in because the level
+// data is built at startup, before the asset server has loaded anything.
+const ECONOMY_FILES: [&str; 8] = [
+    include_str!("../../assets/tower_combinator/levels/economy/level1.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level2.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level3.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level4.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level5.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level6.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level7.ron"),
+    include_str!("../../assets/tower_combinator/levels/economy/level8.ron"),
+];
+
+// Money paid out on top of kill bounties, fields missing from a level file keep their default
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EconomyConfig {
+    // Paid once a wave's enemies are all gone
+    pub wave_bonus: i32,
+    // Added to the wave bonus for every wave cleared so far
+    pub wave_bonus_growth: i32,
+    // Fraction of banked money paid after each cleared wave
+    pub interest_rate: f32,
+    pub interest_cap: i32,
+    // Paid for calling a wave while the previous one is still on the field
+    pub early_call_bonus: i32,
+}
+
+impl EconomyConfig {
+    pub fn parse(text: &str) -> Self {
+        ron::from_str(text).unwrap_or_else(|e| {
+            warn!("Invalid economy config: {e}");
+            Self::default()
+        })
+    }
+}
+
+impl Default for EconomyConfig {
+    fn default() -> Self {
+        Self {
+            wave_bonus: 50,
+            wave_bonus_growth: 10,
+            interest_rate: 0.05,
+            interest_cap: 50,
+            early_call_bonus: 25,
+        }
+    }
 }
 
 impl Default for LevelData {
@@ -59,8 +249,147 @@ impl Default for LevelData {
         Self {
             maps: vec![
                 MAP_TEXT1, MAP_TEXT2, MAP_TEXT3, MAP_TEXT4, MAP_TEXT5, MAP_TEXT6, MAP_TEXT7,
+                MAP_TEXT8,
+            ],
+            enemies: vec![
+                map1(),
+                map2(),
+                map3(),
+                map4(),
+                map5(),
+                map6(),
+                map7(),
+                map8(),
             ],
-            enemies: vec![map1(), map2(), map3(), map4(), map5(), map6(), map7()],
+            economies: ECONOMY_FILES
+                .into_iter()
+                .map(EconomyConfig::parse)
+                .collect(),
+            info: vec![
+                LevelInfo::new("Switchback", "A short winding road to learn the basics."),
+                LevelInfo::new("The Long Haul", "Two long lanes, plenty of room to build."),
+                LevelInfo::new(
+                    "Zigzag",
+                    "The road doubles back on itself, again and again.",
+                ),
+                LevelInfo::new(
+                    "Tangle",
+                    "Tight corners everywhere. Banked money earns double interest.",
+                ),
+                LevelInfo::new("Staircase", "Short steps up a narrow field."),
+                LevelInfo::new(
+                    "Serpent",
+                    "Lean wave bonuses, but calling waves early pays well.",
+                ),
+                LevelInfo::new(
+                    "Labyrinth",
+                    "The last campaign maze. Big wave bonuses and no interest.",
+                ),
+                LevelInfo::new(
+                    "Sabotage",
+                    "Bonus level. Saboteur troopers freeze, short out and douse your towers.",
+                ),
+            ],
+            setups: vec![
+                LevelSetup {
+                    towers: vec![Tower::Piston, Tower::SpikePit, Tower::Water, Tower::Tesla],
+                    preplaced: vec![PreplacedTower::floor(Tower::SpikePit, 1, 0).locked()],
+                    ..default()
+                },
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::Fan,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::Water,
+                        Tower::Tesla,
+                        Tower::Flame,
+                    ],
+                    ..default()
+                },
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::Fan,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::TrapDoor,
+                        Tower::Ice,
+                        Tower::Water,
+                        Tower::Tesla,
+                        Tower::Flame,
+                        Tower::FrostCannon,
+                    ],
+                    ..default()
+                },
+                LevelSetup::default(),
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::Fan,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::Ice,
+                        Tower::Acid,
+                        Tower::Tesla,
+                        Tower::Water,
+                        Tower::Flame,
+                        Tower::Harpoon,
+                    ],
+                    ..default()
+                },
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::Fan,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::TrapDoor,
+                        Tower::Acid,
+                        Tower::Tesla,
+                        Tower::Water,
+                        Tower::Flame,
+                        Tower::AcidSprayer,
+                    ],
+                    money: 300,
+                    ..default()
+                },
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::TrapDoor,
+                        Tower::Ice,
+                        Tower::Tesla,
+                        Tower::Water,
+                        Tower::Flame,
+                        Tower::Harpoon,
+                        Tower::FrostCannon,
+                    ],
+                    money: 500,
+                    lives: 15,
+                    ..default()
+                },
+                LevelSetup {
+                    towers: vec![
+                        Tower::Piston,
+                        Tower::SpikePit,
+                        Tower::Oil,
+                        Tower::Ice,
+                        Tower::Tesla,
+                        Tower::Water,
+                        Tower::Flame,
+                        Tower::Harpoon,
+                        Tower::StunHammer,
+                        Tower::Magnet,
+                    ],
+                    money: 300,
+                    ..default()
+                },
+            ],
+            tutorials: vec![tutorials::first_level(), tutorials::second_level()],
         }
     }
 }
@@ -815,6 +1144,68 @@ pub fn map7() -> VecDeque<Wave> {
     .into()
 }
 
+// Saboteurs, EMP troopers and soakers only show up here, the campaign levels stay as they were
+pub fn map8() -> VecDeque<Wave> {
+    vec![
+        //
+        // Wave 1
+        vec![
+            (vec![basic_trooper(), turbo_trooper()], 0.5),
+            (vec![saboteur_trooper()], 1.0),
+            (vec![basic_trooper(), turbo_trooper()], 0.5),
+            (vec![chonkus_trooper(), basic_trooper()], 0.),
+        ]
+        .into(),
+        // Wave 2
+        vec![
+            (vec![chonkus_trooper()], 0.5),
+            (vec![soaker_trooper(), basic_trooper()], 1.0),
+            (vec![basic_trooper(), turbo_trooper()], 0.5),
+            (vec![turbo_trooper()], 0.5),
+            (vec![chonkus_trooper(), basic_trooper()], 0.5),
+            (vec![soaker_trooper()], 0.75),
+        ]
+        .into(),
+        // Wave 3
+        vec![
+            (vec![emp_trooper(), basic_trooper()], 1.0),
+            (vec![chonkus_trooper(), basic_trooper()], 0.5),
+            (vec![turbo_trooper()], 0.5),
+            (vec![emp_trooper(), saboteur_trooper()], 1.0),
+            (vec![basic_trooper(), turbo_trooper()], 1.5),
+            (vec![chonkus_trooper()], 0.5),
+        ]
+        .into(),
+        // Wave 4
+        vec![
+            (vec![soaker_trooper(), emp_trooper()], 1.0),
+            (vec![saboteur_trooper()], 0.5),
+            (vec![chonkus_trooper(), basic_trooper()], 0.5),
+            (vec![basic_trooper(), turbo_trooper()], 0.5),
+            (vec![turbo_trooper()], 0.5),
+            (vec![chonkus_trooper(), basic_trooper()], 0.75),
+            (vec![saboteur_trooper(), soaker_trooper()], 1.0),
+        ]
+        .into(),
+        // Wave 5
+        vec![
+            (vec![emp_trooper(), saboteur_trooper()], 1.0),
+            (vec![chonkus_trooper(), basic_trooper()], 0.5),
+            (vec![soaker_trooper(), emp_trooper()], 1.0),
+            (vec![turbo_trooper()], 0.5),
+            (
+                vec![chonkus_trooper(), basic_trooper(), turbo_trooper()],
+                1.5,
+            ),
+            (vec![saboteur_trooper(), saboteur_trooper()], 1.0),
+            (vec![chonkus_trooper(), basic_trooper()], 0.5),
+            (vec![basic_trooper(), turbo_trooper()], 0.75),
+        ]
+        .into(),
+    ]
+    .into()
+}
+
 /*
 
 (vec![chonkus_trooper(), basic_trooper(), turbo_trooper()], 0.5),
@@ -833,3 +1224,29 @@ pub fn map7() -> VecDeque<Wave> {
 
 (vec![ turbo_trooper()], 0.5),
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_level_has_a_valid_economy_file() {
+        let level_data = LevelData::default();
+        assert_eq!(ECONOMY_FILES.len(), level_data.maps.len());
+        for text in ECONOMY_FILES {
+            assert!(ron::from_str::<EconomyConfig>(text).is_ok(), "{text}");
+        }
+    }
+
+    #[test]
+    fn missing_economy_fields_keep_their_defaults() {
+        let config = EconomyConfig::parse("(wave_bonus: 10)");
+        assert_eq!(
+            config,
+            EconomyConfig {
+                wave_bonus: 10,
+                ..default()
+            }
+        );
+    }
+}
@@ -6,17 +6,20 @@ pub use status_effects::{StatusEffect, StatusEffectTrait};
 
 mod input_state;
 pub mod levels;
+pub mod placement;
 pub mod projectiles;
 mod state;
 pub mod stats;
 pub mod status_effects;
+pub mod synergies;
 mod towers;
+pub mod tutorials;
 
 pub use {
     input_state::PointerInteractionState,
     state::PlayerState,
     towers::get_collision,
-    towers::{Tower, TowerCollision},
+    towers::{Tower, TowerArt, TowerCollision},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -0,0 +1,268 @@
+use super::Tower;
+use crate::level::{
+    components::{Adjacent, ExactPosition},
+    resource::CellDirection,
+};
+
+// Placement rules only look at plain data, so anything that can describe a level's
+// towers (the game, a simulator, an editor) can ask whether a placement is legal.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PlacedTower {
+    pub tower: Tower,
+    pub piece: Adjacent,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlacementContext<'a> {
+    pub tower: Tower,
+    pub piece: Adjacent,
+    pub orientation: CellDirection,
+    pub money: i32,
+    pub placed: &'a [PlacedTower],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PlacementVerdict {
+    Allowed,
+    Denied(&'static str),
+}
+
+impl PlacementVerdict {
+    pub fn is_allowed(&self) -> bool {
+        *self == PlacementVerdict::Allowed
+    }
+
+    pub fn reason(&self) -> Option<&'static str> {
+        match self {
+            PlacementVerdict::Allowed => None,
+            PlacementVerdict::Denied(reason) => Some(reason),
+        }
+    }
+}
+
+pub trait PlacementRule: Send + Sync {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict;
+}
+
+fn deny_if(condition: bool, reason: &'static str) -> PlacementVerdict {
+    if condition {
+        PlacementVerdict::Denied(reason)
+    } else {
+        PlacementVerdict::Allowed
+    }
+}
+
+pub struct Affordable;
+
+impl PlacementRule for Affordable {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(context.money < context.tower.price(), "Insufficient funds")
+    }
+}
+
+pub struct Unoccupied;
+
+impl PlacementRule for Unoccupied {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(
+            context.placed.iter().any(|w| w.piece == context.piece),
+            "There is already a tower here",
+        )
+    }
+}
+
+pub struct SupportedOrientation;
+
+impl PlacementRule for SupportedOrientation {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(
+            !context.tower.orientations().contains(&context.orientation),
+            "This tower can't be mounted facing that way",
+        )
+    }
+}
+
+// Towers spanning both sides of a floor need a cell below them
+pub struct NotOnBottom;
+
+impl PlacementRule for NotOnBottom {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(
+            context.tower.spans_both_sides()
+                && context.piece.exact_position == ExactPosition::Floor
+                && context.piece.id.unit_y == 0,
+            "This tower cannot be placed on the bottom",
+        )
+    }
+}
+
+pub struct BothSidesFree;
+
+impl PlacementRule for BothSidesFree {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(
+            context.tower.spans_both_sides()
+                && context
+                    .placed
+                    .iter()
+                    .any(|w| w.piece.id == context.piece.id),
+            "This tower requires both sides of the wall",
+        )
+    }
+}
+
+pub struct NotAgainstSpanningTower;
+
+impl PlacementRule for NotAgainstSpanningTower {
+    fn check(&self, context: &PlacementContext) -> PlacementVerdict {
+        deny_if(
+            context
+                .placed
+                .iter()
+                .any(|w| w.tower.spans_both_sides() && w.piece.id == context.piece.id),
+            "Cannot place this tower under a trap door",
+        )
+    }
+}
+
+pub fn placement_rules() -> Vec<Box<dyn PlacementRule>> {
+    vec![
+        Box::new(Affordable),
+        Box::new(Unoccupied),
+        Box::new(SupportedOrientation),
+        Box::new(NotOnBottom),
+        Box::new(BothSidesFree),
+        Box::new(NotAgainstSpanningTower),
+    ]
+}
+
+// The first rule that objects decides the verdict
+pub fn evaluate(rules: &[Box<dyn PlacementRule>], context: &PlacementContext) -> PlacementVerdict {
+    rules
+        .iter()
+        .map(|rule| rule.check(context))
+        .find(|verdict| !verdict.is_allowed())
+        .unwrap_or(PlacementVerdict::Allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::components::{GeneralPosition, WallDirection};
+
+    fn floor(x: usize, y: usize) -> Adjacent {
+        Adjacent::new(x, y, GeneralPosition::UpDown, ExactPosition::Floor)
+    }
+
+    fn ceiling(x: usize, y: usize) -> Adjacent {
+        Adjacent::new(x, y, GeneralPosition::UpDown, ExactPosition::Ceiling)
+    }
+
+    fn verdict(
+        tower: Tower,
+        piece: Adjacent,
+        money: i32,
+        placed: &[PlacedTower],
+    ) -> PlacementVerdict {
+        evaluate(
+            &placement_rules(),
+            &PlacementContext {
+                tower,
+                piece,
+                orientation: piece.exact_position.mount_direction(),
+                money,
+                placed,
+            },
+        )
+    }
+
+    #[test]
+    fn allows_a_legal_placement() {
+        assert_eq!(
+            verdict(Tower::Piston, floor(1, 1), 1000, &[]),
+            PlacementVerdict::Allowed
+        );
+    }
+
+    #[test]
+    fn denies_unaffordable_towers() {
+        let price = Tower::Piston.price();
+        assert_eq!(
+            verdict(Tower::Piston, floor(1, 1), price - 1, &[]).reason(),
+            Some("Insufficient funds")
+        );
+        assert!(verdict(Tower::Piston, floor(1, 1), price, &[]).is_allowed());
+    }
+
+    #[test]
+    fn denies_occupied_pieces() {
+        let placed = [PlacedTower {
+            tower: Tower::Fan,
+            piece: floor(1, 1),
+        }];
+        assert_eq!(
+            verdict(Tower::Piston, floor(1, 1), 1000, &placed).reason(),
+            Some("There is already a tower here")
+        );
+    }
+
+    #[test]
+    fn denies_unsupported_orientations() {
+        let wall = Adjacent::new(
+            2,
+            1,
+            GeneralPosition::LeftRight,
+            ExactPosition::Wall(WallDirection::Left),
+        );
+        assert_eq!(
+            verdict(Tower::SpikePit, wall, 1000, &[]).reason(),
+            Some("This tower can't be mounted facing that way")
+        );
+    }
+
+    #[test]
+    fn trap_doors_need_a_cell_below() {
+        assert_eq!(
+            verdict(Tower::TrapDoor, floor(1, 0), 1000, &[]).reason(),
+            Some("This tower cannot be placed on the bottom")
+        );
+        assert!(verdict(Tower::TrapDoor, floor(1, 1), 1000, &[]).is_allowed());
+    }
+
+    #[test]
+    fn trap_doors_need_both_sides_free() {
+        let placed = [PlacedTower {
+            tower: Tower::Oil,
+            piece: ceiling(1, 1),
+        }];
+        assert_eq!(
+            verdict(Tower::TrapDoor, floor(1, 1), 1000, &placed).reason(),
+            Some("This tower requires both sides of the wall")
+        );
+    }
+
+    #[test]
+    fn nothing_goes_under_a_trap_door() {
+        let placed = [PlacedTower {
+            tower: Tower::TrapDoor,
+            piece: floor(1, 1),
+        }];
+        assert_eq!(
+            verdict(Tower::Oil, ceiling(1, 1), 1000, &placed).reason(),
+            Some("Cannot place this tower under a trap door")
+        );
+    }
+
+    #[test]
+    fn the_first_objection_wins() {
+        let placed = [PlacedTower {
+            tower: Tower::Fan,
+            piece: floor(1, 1),
+        }];
+        assert_eq!(
+            verdict(Tower::Piston, floor(1, 1), 0, &placed).reason(),
+            Some("Insufficient funds")
+        );
+    }
+}
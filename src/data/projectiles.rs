@@ -1,4 +1,5 @@
 use bevy::{color::palettes::css::*, prelude::*};
+use serde::{Deserialize, Serialize};
 use std::marker::ConstParamTy_;
 use std::marker::UnsizedConstParamTy;
 use std::{fmt::Display, sync::Arc};
@@ -13,6 +14,24 @@ pub enum TowerAttackType {
     Contact(Vec<AttackSpecification>),
     DropsLiquid(LiquidType),
     ModifiesSelf,
+    Projectile(Vec<AttackSpecification>),
+}
+
+impl TowerAttackType {
+    pub fn damage_types(&self) -> Vec<DamageType> {
+        match self {
+            TowerAttackType::EntireCell(specs)
+            | TowerAttackType::Contact(specs)
+            | TowerAttackType::Projectile(specs) => specs
+                .iter()
+                .filter_map(|w| match w {
+                    AttackSpecification::Damage(damage_type, _) => Some(*damage_type),
+                    _ => None,
+                })
+                .collect(),
+            TowerAttackType::DropsLiquid(_) | TowerAttackType::ModifiesSelf => vec![],
+        }
+    }
 }
 
 #[derive(Clone, Debug, Reflect, PartialEq)]
@@ -22,7 +41,35 @@ pub enum AttackSpecification {
     Status(StatusEnum),
 }
 
-#[derive(Clone, Debug, Reflect, PartialEq)]
+impl AttackSpecification {
+    // Turns a tower's attack definition into concrete data, scaled by the tower's stats
+    pub fn resolve(
+        &self,
+        direction: CellDirection,
+        strength: usize,
+        damage_mul: f32,
+        push_mul: f32,
+    ) -> AttackData {
+        match self {
+            AttackSpecification::Damage(damage_type, damage) => AttackData::Damage {
+                dmg_type: *damage_type,
+                strength,
+                damage: (*damage as f32 * damage_mul).round() as usize,
+            },
+            AttackSpecification::Push(force) => AttackData::Push {
+                direction,
+                strength,
+                force: *force * push_mul,
+            },
+            AttackSpecification::Status(status_enum) => AttackData::Status {
+                status: *status_enum,
+                strength,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Reflect, PartialEq, Serialize, Deserialize)]
 pub enum AttackData {
     Damage {
         dmg_type: DamageType,
@@ -46,14 +93,40 @@ pub struct Droplet(pub LiquidType);
 #[derive(Component, Copy, Clone, Debug, Reflect, PartialEq, Eq)]
 pub struct Puddle(pub LiquidType);
 
-#[derive(Copy, Clone, Debug, Reflect, PartialEq, Eq)]
+// The dropping tower's stats, carried from droplet to puddle
+#[derive(Component, Copy, Clone, Debug, Reflect, PartialEq, Serialize, Deserialize)]
+pub struct LiquidPotency {
+    pub strength: usize,
+    pub damage_mul: f32,
+}
+
+impl Default for LiquidPotency {
+    fn default() -> Self {
+        Self {
+            strength: 1,
+            damage_mul: 1.,
+        }
+    }
+}
+
+// Fired by ranged towers, carries the tower's attack resolved at the time it fired
+#[derive(Component, Clone, Debug, Reflect, PartialEq)]
+pub struct Projectile {
+    pub tower: Entity,
+    pub mount: Entity,
+    pub effects: Vec<AttackData>,
+}
+
+#[derive(Copy, Clone, Debug, Reflect, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LiquidType {
     Water,
     Oil,
     Acid,
 }
 
-#[derive(Copy, Clone, Debug, Reflect, PartialEq, Eq, UnsizedConstParamTy)]
+#[derive(
+    Copy, Clone, Debug, Reflect, PartialEq, Eq, UnsizedConstParamTy, Serialize, Deserialize,
+)]
 pub enum DamageType {
     Physical,
     Burning,
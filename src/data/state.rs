@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Resource, Clone, Copy, Reflect, Debug)]
+#[derive(Resource, Clone, Copy, Reflect, Debug, Serialize, Deserialize)]
 pub struct PlayerState {
     pub money: i32,
     pub health: i32,
+    pub max_health: i32,
 }
 
 impl Default for PlayerState {
@@ -11,6 +13,7 @@ impl Default for PlayerState {
         PlayerState {
             money: 400,
             health: 25,
+            max_health: 25,
         }
     }
 }
@@ -19,4 +22,15 @@ impl PlayerState {
     pub fn can_afford(&self, cost: i32) -> bool {
         self.money >= cost
     }
+
+    // Three stars for a flawless level, two for keeping at least half the lives
+    pub fn stars(&self) -> u8 {
+        if self.health >= self.max_health {
+            3
+        } else if self.health * 2 >= self.max_health {
+            2
+        } else {
+            1
+        }
+    }
 }
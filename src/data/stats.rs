@@ -86,6 +86,11 @@ impl<T: StatTrait> Stat<T> {
 define_stat!(MoveSpeed, "Move Speed");
 define_stat!(StatFriction, "Friction");
 define_stat!(DamageMultiplierAll, "Global Damage Multiplier");
+define_stat!(FireRate, "Fire Rate");
+define_stat!(AttackDamage, "Attack Damage");
+define_stat!(PushForce, "Push Force");
+define_stat!(TriggerRange, "Trigger Range");
+define_stat!(AttackStrength, "Attack Strength");
 
 #[derive(Component, Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
 pub struct DamageMultiplier<const G: DamageType>;
@@ -1,7 +1,8 @@
-use std::{default, marker::PhantomData};
+use std::{default, marker::PhantomData, time::Duration};
 
 use crate::define_status_effect;
 use bevy::{color::palettes::css::*, prelude::*, reflect::GetTypeRegistration};
+use serde::{Deserialize, Serialize};
 
 use super::projectiles::DamageType;
 
@@ -21,10 +22,18 @@ pub trait StatusEffectTrait:
     fn corresponding_enum() -> StatusEnum;
 }
 
-#[derive(Component, Eq, PartialEq, Debug, Reflect, Clone)]
+pub const DOT_TICK_SECONDS: f32 = 0.5;
+
+#[derive(Component, PartialEq, Debug, Reflect, Clone)]
 pub struct StatusEffect<T: StatusEffectTrait> {
     pub strength: usize,
     pub duration: Timer,
+    // Damage over time ticks from when this effect was applied
+    pub dot_tick: Timer,
+    // Fractional damage carried over between ticks
+    pub dot_remainder: f32,
+    // The tower or puddle that applied this effect, credited for its damage over time
+    pub source: Option<Entity>,
     #[reflect(ignore)]
     _phantom: PhantomData<T>,
 }
@@ -34,9 +43,30 @@ impl<T: StatusEffectTrait> StatusEffect<T> {
         StatusEffect {
             strength,
             duration: Timer::from_seconds(duration, TimerMode::Once),
+            dot_tick: Timer::from_seconds(DOT_TICK_SECONDS, TimerMode::Repeating),
+            dot_remainder: 0.,
+            source: None,
             _phantom: PhantomData,
         }
     }
+
+    pub fn with_source(mut self, source: Option<Entity>) -> Self {
+        self.source = source;
+        self
+    }
+
+    // Returns the whole damage due this frame for the given damage per second
+    pub fn accumulate_dot(&mut self, delta: Duration, dps: f32) -> isize {
+        self.dot_tick.tick(delta);
+        let ticks = self.dot_tick.times_finished_this_tick();
+        if ticks == 0 {
+            return 0;
+        }
+        self.dot_remainder += dps * DOT_TICK_SECONDS * ticks as f32;
+        let damage = self.dot_remainder.floor();
+        self.dot_remainder -= damage;
+        damage as isize
+    }
 }
 
 define_status_effect!(Wet, "Wet", BLUE.into(), 4., DamageType::Cold);
@@ -59,8 +89,30 @@ define_status_effect!(
     DamageType::Chemical
 );
 define_status_effect!(Oiled, "Oiled", BROWN.into(), 4.0, DamageType::Chemical);
+define_status_effect!(Stunned, "Stunned", WHITE.into(), 0.6, DamageType::Physical);
+define_status_effect!(
+    Poisoned,
+    "Poisoned",
+    PURPLE.into(),
+    5.0,
+    DamageType::Chemical
+);
+define_status_effect!(
+    Magnetized,
+    "Magnetized",
+    STEEL_BLUE.into(),
+    3.0,
+    DamageType::Lightning
+);
+define_status_effect!(
+    Slippery,
+    "Slippery",
+    KHAKI.into(),
+    4.0,
+    DamageType::Chemical
+);
 
-#[derive(Clone, Copy, Debug, Reflect, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Reflect, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StatusEnum {
     Wet,
     Ignited,
@@ -70,6 +122,218 @@ pub enum StatusEnum {
     Electrocuted,
     Acidified,
     Oiled,
+    Stunned,
+    Poisoned,
+    Magnetized,
+    Slippery,
+}
+
+impl StatusEnum {
+    pub fn color(&self) -> Color {
+        match self {
+            StatusEnum::Wet => Wet::color(),
+            StatusEnum::Ignited => Ignited::color(),
+            StatusEnum::Burned => Burned::color(),
+            StatusEnum::Chilled => Chilled::color(),
+            StatusEnum::Frozen => Frozen::color(),
+            StatusEnum::Electrocuted => Electrocuted::color(),
+            StatusEnum::Acidified => Acidified::color(),
+            StatusEnum::Oiled => Oiled::color(),
+            StatusEnum::Stunned => Stunned::color(),
+            StatusEnum::Poisoned => Poisoned::color(),
+            StatusEnum::Magnetized => Magnetized::color(),
+            StatusEnum::Slippery => Slippery::color(),
+        }
+    }
+
+    pub fn all() -> Vec<StatusEnum> {
+        vec![
+            StatusEnum::Wet,
+            StatusEnum::Ignited,
+            StatusEnum::Burned,
+            StatusEnum::Chilled,
+            StatusEnum::Frozen,
+            StatusEnum::Electrocuted,
+            StatusEnum::Acidified,
+            StatusEnum::Oiled,
+            StatusEnum::Stunned,
+            StatusEnum::Poisoned,
+            StatusEnum::Magnetized,
+            StatusEnum::Slippery,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusEnum::Wet => Wet::name(),
+            StatusEnum::Ignited => Ignited::name(),
+            StatusEnum::Burned => Burned::name(),
+            StatusEnum::Chilled => Chilled::name(),
+            StatusEnum::Frozen => Frozen::name(),
+            StatusEnum::Electrocuted => Electrocuted::name(),
+            StatusEnum::Acidified => Acidified::name(),
+            StatusEnum::Oiled => Oiled::name(),
+            StatusEnum::Stunned => Stunned::name(),
+            StatusEnum::Poisoned => Poisoned::name(),
+            StatusEnum::Magnetized => Magnetized::name(),
+            StatusEnum::Slippery => Slippery::name(),
+        }
+    }
+
+    pub fn base_duration(&self) -> f32 {
+        match self {
+            StatusEnum::Wet => Wet::base_duration(),
+            StatusEnum::Ignited => Ignited::base_duration(),
+            StatusEnum::Burned => Burned::base_duration(),
+            StatusEnum::Chilled => Chilled::base_duration(),
+            StatusEnum::Frozen => Frozen::base_duration(),
+            StatusEnum::Electrocuted => Electrocuted::base_duration(),
+            StatusEnum::Acidified => Acidified::base_duration(),
+            StatusEnum::Oiled => Oiled::base_duration(),
+            StatusEnum::Stunned => Stunned::base_duration(),
+            StatusEnum::Poisoned => Poisoned::base_duration(),
+            StatusEnum::Magnetized => Magnetized::base_duration(),
+            StatusEnum::Slippery => Slippery::base_duration(),
+        }
+    }
+}
+
+// Stats a status effect scales while it is applied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebuffStat {
+    MoveSpeed,
+    Friction,
+    FireRate,
+    DamageTaken(DamageType),
+    AllDamageTaken,
+}
+
+impl DebuffStat {
+    pub fn name(&self) -> String {
+        match self {
+            DebuffStat::MoveSpeed => "Move speed".to_string(),
+            DebuffStat::Friction => "Friction".to_string(),
+            DebuffStat::FireRate => "Tower fire rate".to_string(),
+            DebuffStat::DamageTaken(damage_type) => format!("{damage_type:?} damage taken"),
+            DebuffStat::AllDamageTaken => "All damage taken".to_string(),
+        }
+    }
+}
+
+// Multipliers applied by each status, read by both the stat systems and the codex
+pub const STATUS_DEBUFFS: &[(StatusEnum, DebuffStat, f32)] = &[
+    (StatusEnum::Frozen, DebuffStat::MoveSpeed, 0.),
+    (StatusEnum::Frozen, DebuffStat::Friction, 0.),
+    (
+        StatusEnum::Frozen,
+        DebuffStat::DamageTaken(DamageType::Physical),
+        4.,
+    ),
+    (StatusEnum::Chilled, DebuffStat::MoveSpeed, 0.5),
+    (StatusEnum::Wet, DebuffStat::MoveSpeed, 0.9),
+    (
+        StatusEnum::Wet,
+        DebuffStat::DamageTaken(DamageType::Lightning),
+        1.5,
+    ),
+    (StatusEnum::Oiled, DebuffStat::Friction, 0.),
+    (StatusEnum::Burned, DebuffStat::AllDamageTaken, 1.10),
+    (
+        StatusEnum::Burned,
+        DebuffStat::DamageTaken(DamageType::Cold),
+        1.35,
+    ),
+    (StatusEnum::Acidified, DebuffStat::AllDamageTaken, 1.05),
+    (
+        StatusEnum::Acidified,
+        DebuffStat::DamageTaken(DamageType::Burning),
+        1.10,
+    ),
+    (
+        StatusEnum::Acidified,
+        DebuffStat::DamageTaken(DamageType::Chemical),
+        1.10,
+    ),
+    (StatusEnum::Stunned, DebuffStat::MoveSpeed, 0.),
+    (StatusEnum::Slippery, DebuffStat::Friction, 0.),
+    (StatusEnum::Frozen, DebuffStat::FireRate, 0.),
+    (StatusEnum::Electrocuted, DebuffStat::FireRate, 0.),
+    (StatusEnum::Wet, DebuffStat::FireRate, 0.5),
+];
+
+pub fn status_debuffs(status: StatusEnum) -> impl Iterator<Item = (DebuffStat, f32)> {
+    STATUS_DEBUFFS
+        .iter()
+        .filter(move |(w, _, _)| *w == status)
+        .map(|(_, stat, multiplier)| (*stat, *multiplier))
+}
+
+pub fn status_debuff(status: StatusEnum, stat: DebuffStat) -> f32 {
+    status_debuffs(status)
+        .find(|(w, _)| *w == stat)
+        .map_or(1., |(_, multiplier)| multiplier)
+}
+
+// Combinations of statuses that turn into something stronger
+#[derive(Clone, Copy, Debug, Reflect, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Reaction {
+    Ignite,
+    Freeze,
+    ChainLightning,
+    Poison,
+    Slick,
+    Slam,
+}
+
+impl Reaction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reaction::Ignite => "Ignite",
+            Reaction::Freeze => "Freeze",
+            Reaction::ChainLightning => "Chain Lightning",
+            Reaction::Poison => "Poison",
+            Reaction::Slick => "Slick",
+            Reaction::Slam => "Slam",
+        }
+    }
+
+    pub fn all() -> Vec<Reaction> {
+        vec![
+            Reaction::Ignite,
+            Reaction::Freeze,
+            Reaction::ChainLightning,
+            Reaction::Poison,
+            Reaction::Slick,
+            Reaction::Slam,
+        ]
+    }
+
+    pub fn recipe(&self) -> &'static str {
+        match self {
+            Reaction::Ignite => "Burn an oiled enemy to set it Ignited.",
+            Reaction::Freeze => "Chill a wet enemy to freeze it solid.",
+            Reaction::ChainLightning => {
+                "When Electrocuted wears off, lightning arcs to nearby enemies."
+            }
+            Reaction::Poison => "Acid on an oiled enemy turns into Poison.",
+            Reaction::Slick => "Water on an oiled enemy makes it Slippery.",
+            Reaction::Slam => {
+                "A piston shoving an enemy caught in a fan's wind sends it much further."
+            }
+        }
+    }
+
+    // The status whose icon stands for this reaction
+    pub fn icon_status(&self) -> StatusEnum {
+        match self {
+            Reaction::Ignite => StatusEnum::Ignited,
+            Reaction::Freeze => StatusEnum::Frozen,
+            Reaction::ChainLightning => StatusEnum::Electrocuted,
+            Reaction::Poison => StatusEnum::Poisoned,
+            Reaction::Slick => StatusEnum::Slippery,
+            Reaction::Slam => StatusEnum::Stunned,
+        }
+    }
 }
 
 #[macro_export]
@@ -123,3 +387,36 @@ pub fn damage_multiplier(strength: usize) -> f32 {
         _ => 4.,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn dot_waits_for_a_full_tick() {
+        let mut effect = StatusEffect::<Burned>::new(1, 4.);
+        let half_tick = seconds(DOT_TICK_SECONDS / 2.);
+        assert_eq!(effect.accumulate_dot(half_tick, 10.), 0);
+        assert_eq!(effect.accumulate_dot(half_tick, 10.), 5);
+    }
+
+    #[test]
+    fn dot_carries_fractional_damage_over() {
+        let mut effect = StatusEffect::<Burned>::new(1, 4.);
+        // 3 damage per second is 1.5 per tick
+        assert_eq!(effect.accumulate_dot(seconds(DOT_TICK_SECONDS), 3.), 1);
+        assert_eq!(effect.dot_remainder, 0.5);
+        assert_eq!(effect.accumulate_dot(seconds(DOT_TICK_SECONDS), 3.), 2);
+        assert_eq!(effect.dot_remainder, 0.);
+    }
+
+    #[test]
+    fn dot_catches_up_on_long_frames() {
+        let mut effect = StatusEffect::<Burned>::new(1, 4.);
+        assert_eq!(effect.accumulate_dot(seconds(DOT_TICK_SECONDS * 3.), 2.), 3);
+    }
+}
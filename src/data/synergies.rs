@@ -0,0 +1,89 @@
+use bevy::{color::palettes::css::*, prelude::*};
+
+use super::Tower;
+
+// How two towers need to be laid out for a synergy to kick in
+#[derive(Clone, Copy, Debug, Reflect, PartialEq, Eq)]
+pub enum SynergyRelation {
+    // Same cell or one of the four cells around it
+    Neighbor,
+    // Same cell or the cell directly above
+    Above,
+}
+
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub enum SynergyBonus {
+    Strength(usize),
+    TriggerRange(f32),
+    FireRate(f32),
+}
+
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub struct Synergy {
+    pub source: Tower,
+    pub target: Tower,
+    pub relation: SynergyRelation,
+    pub bonus: SynergyBonus,
+}
+
+impl Synergy {
+    pub fn all() -> Vec<Synergy> {
+        vec![
+            Synergy {
+                source: Tower::Water,
+                target: Tower::Tesla,
+                relation: SynergyRelation::Neighbor,
+                bonus: SynergyBonus::Strength(1),
+            },
+            Synergy {
+                source: Tower::Fan,
+                target: Tower::Flame,
+                relation: SynergyRelation::Neighbor,
+                bonus: SynergyBonus::TriggerRange(1.5),
+            },
+            Synergy {
+                source: Tower::Oil,
+                target: Tower::Flame,
+                relation: SynergyRelation::Above,
+                bonus: SynergyBonus::FireRate(1.5),
+            },
+        ]
+    }
+
+    // Every bonus `source` at `source_cell` grants `target` at `target_cell`
+    pub fn between(
+        source: Tower,
+        source_cell: IVec2,
+        target: Tower,
+        target_cell: IVec2,
+    ) -> impl Iterator<Item = SynergyBonus> {
+        Synergy::all()
+            .into_iter()
+            .filter(move |w| {
+                w.source == source
+                    && w.target == target
+                    && w.relation.holds(source_cell, target_cell)
+            })
+            .map(|w| w.bonus)
+    }
+}
+
+impl SynergyRelation {
+    pub fn holds(&self, source_cell: IVec2, target_cell: IVec2) -> bool {
+        let offset = source_cell - target_cell;
+        match self {
+            SynergyRelation::Neighbor => offset.x.abs() + offset.y.abs() <= 1,
+            SynergyRelation::Above => offset.x == 0 && (0..=1).contains(&offset.y),
+        }
+    }
+}
+
+impl SynergyBonus {
+    pub fn color(&self) -> Color {
+        match self {
+            SynergyBonus::Strength(_) => YELLOW.into(),
+            SynergyBonus::TriggerRange(_) => ORANGE.into(),
+            SynergyBonus::FireRate(_) => LIME.into(),
+        }
+    }
+}
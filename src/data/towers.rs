@@ -1,15 +1,21 @@
 use std::sync::Arc;
 
-use bevy::prelude::*;
+use bevy::{color::palettes::css, prelude::*};
+use serde::{Deserialize, Serialize};
 
-use crate::assets::{SoundEffects, sound_effects::SoundFn};
+use crate::{
+    assets::{SoundEffects, sound_effects::SoundFn},
+    level::resource::CellDirection,
+};
 
 use super::{
     projectiles::{AttackSpecification, DamageType, LiquidType, TowerAttackType},
     status_effects::StatusEnum,
 };
 
-#[derive(Component, Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect)]
+#[derive(
+    Component, Copy, Clone, Eq, PartialEq, Hash, Debug, Reflect, Serialize, Deserialize,
+)]
 pub enum Tower {
     Piston,
     Fan,
@@ -22,6 +28,11 @@ pub enum Tower {
     Water,
     Flame,
     Portal,
+    Harpoon,
+    FrostCannon,
+    AcidSprayer,
+    StunHammer,
+    Magnet,
 }
 
 impl Tower {
@@ -37,6 +48,11 @@ impl Tower {
             Tower::Tesla,
             Tower::Water,
             Tower::Flame,
+            Tower::Harpoon,
+            Tower::FrostCannon,
+            Tower::AcidSprayer,
+            Tower::StunHammer,
+            Tower::Magnet,
         ]
     }
 
@@ -53,24 +69,34 @@ impl Tower {
             Tower::Water => "Water Bucket",
             Tower::Flame => "Flame",
             Tower::Portal => "Portal",
+            Tower::Harpoon => "Harpoon",
+            Tower::FrostCannon => "Frost Cannon",
+            Tower::AcidSprayer => "Acid Sprayer",
+            Tower::StunHammer => "Stun Hammer",
+            Tower::Magnet => "Electromagnet",
         }
     }
 
     pub fn description(&self) -> &'static str {
         match self {
             Tower::Piston => "Shoves enemies with strong mechanical force, and shatters ice.",
-            Tower::Fan => "Pushes against enemies. Use when they can't move.",
+            Tower::Fan => "Blows enemies along its wind.",
             Tower::SpikePit => "Triggers hidden spikes to damage passing enemies.",
-            Tower::Oil => "Oil makes enemies slippery and flammable.",
+            Tower::Oil => "Oil makes enemies flammable. Wash it with water to make them slippery.",
             Tower::TrapDoor => "50% chance to let enemies through.",
             Tower::Ice => "Slows enemies and freezes water.",
-            Tower::Acid => "Acid weakens enemies to all damage.",
+            Tower::Acid => "Acid weakens enemies to all damage. Poisons oiled enemies.",
             Tower::Tesla => "Shocks enemies. Ignites oil and bounces off water.",
             Tower::Water => "Slows and damages, empowers lightning and ice.",
             Tower::Flame => {
                 "Burns enemies, ignites oil. Enemies cooked without oil are weak to cold."
             }
             Tower::Portal => "Teleports enemies backward along their path.",
+            Tower::Harpoon => "Harpoons and knocks back the first enemy in its long line of sight.",
+            Tower::FrostCannon => "Lobs frost shells across the room, chilling whatever they hit.",
+            Tower::AcidSprayer => "Sprays acid a few cells out. Walls block its aim.",
+            Tower::StunHammer => "Knocks enemies senseless, stopping them in their tracks.",
+            Tower::Magnet => "Magnetizes enemies so they drag their neighbours together.",
         }
     }
 
@@ -87,6 +113,11 @@ impl Tower {
             Tower::Water => 50,
             Tower::Flame => 40,
             Tower::Portal => 150,
+            Tower::Harpoon => 80,
+            Tower::FrostCannon => 120,
+            Tower::AcidSprayer => 70,
+            Tower::StunHammer => 60,
+            Tower::Magnet => 55,
         }
     }
 
@@ -103,12 +134,45 @@ impl Tower {
             Tower::Water => "icon_water_bucket",
             Tower::Flame => "icon_flame",
             Tower::Portal => "icon_portal",
+            Tower::Harpoon => "icon_piston",
+            Tower::FrostCannon => "icon_ice",
+            Tower::AcidSprayer => "icon_acid",
+            Tower::StunHammer => "icon_piston",
+            Tower::Magnet => "icon_tesla",
+        }
+    }
+
+    // Newer towers reuse the art of their closest cousin, tinted
+    pub fn sprite_tower(&self) -> TowerArt {
+        match self {
+            Tower::Piston | Tower::Harpoon | Tower::StunHammer => TowerArt::Piston,
+            Tower::Fan => TowerArt::Fan,
+            Tower::SpikePit => TowerArt::SpikePit,
+            Tower::Oil => TowerArt::Oil,
+            Tower::TrapDoor => TowerArt::TrapDoor,
+            Tower::Ice | Tower::FrostCannon => TowerArt::Ice,
+            Tower::Acid | Tower::AcidSprayer => TowerArt::Acid,
+            Tower::Tesla | Tower::Magnet => TowerArt::Tesla,
+            Tower::Water => TowerArt::Water,
+            Tower::Flame => TowerArt::Flame,
+            Tower::Portal => TowerArt::Portal,
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            Tower::Harpoon => css::LIGHT_STEEL_BLUE.into(),
+            Tower::FrostCannon => css::LIGHT_CYAN.into(),
+            Tower::AcidSprayer => css::GREEN_YELLOW.into(),
+            Tower::StunHammer => css::GOLD.into(),
+            Tower::Magnet => css::STEEL_BLUE.into(),
+            _ => Color::WHITE,
         }
     }
 
     pub fn has_trigger_zone(&self) -> bool {
         match self {
-            Tower::Fan => false,
+            Tower::Fan | Tower::Harpoon | Tower::FrostCannon | Tower::AcidSprayer => false,
             _ => true,
         }
     }
@@ -127,13 +191,23 @@ impl Tower {
         }
     }
 
+    // How many cells a ranged tower can see down its line of fire
+    pub fn range(&self) -> Option<f32> {
+        match self {
+            Tower::Harpoon => Some(6.),
+            Tower::FrostCannon => Some(4.),
+            Tower::AcidSprayer => Some(3.),
+            _ => None,
+        }
+    }
+
     pub fn attack_def(&self) -> TowerAttackType {
         match self {
             Tower::Piston => TowerAttackType::EntireCell(vec![
                 AttackSpecification::Damage(DamageType::Physical, 10),
                 AttackSpecification::Push(800.),
             ]),
-            Tower::Fan => TowerAttackType::EntireCell(vec![AttackSpecification::Push(10.)]),
+            Tower::Fan => TowerAttackType::EntireCell(vec![AttackSpecification::Push(45.)]),
             //Tower::SpikePit => TowerAttackType::Contact(vec![AttackSpecification::Damage(
             //    DamageType::Physical,
             //    10,
@@ -164,6 +238,25 @@ impl Tower {
                 AttackSpecification::Status(StatusEnum::Burned),
             ]),
             Tower::Portal => todo!(),
+            Tower::Harpoon => TowerAttackType::Projectile(vec![
+                AttackSpecification::Damage(DamageType::Physical, 25),
+                AttackSpecification::Push(400.),
+            ]),
+            Tower::FrostCannon => TowerAttackType::Projectile(vec![
+                AttackSpecification::Damage(DamageType::Cold, 10),
+                AttackSpecification::Status(StatusEnum::Chilled),
+            ]),
+            Tower::AcidSprayer => TowerAttackType::Projectile(vec![
+                AttackSpecification::Damage(DamageType::Chemical, 5),
+                AttackSpecification::Status(StatusEnum::Acidified),
+            ]),
+            Tower::StunHammer => TowerAttackType::EntireCell(vec![
+                AttackSpecification::Damage(DamageType::Physical, 5),
+                AttackSpecification::Status(StatusEnum::Stunned),
+            ]),
+            Tower::Magnet => TowerAttackType::EntireCell(vec![AttackSpecification::Status(
+                StatusEnum::Magnetized,
+            )]),
         }
     }
 
@@ -180,17 +273,47 @@ impl Tower {
             Tower::Water => 5.0,
             Tower::Flame => 0.67,
             Tower::Portal => 3.0,
+            Tower::Harpoon => 2.5,
+            Tower::FrostCannon => 1.5,
+            Tower::AcidSprayer => 1.0,
+            Tower::StunHammer => 2.5,
+            Tower::Magnet => 1.5,
         }
     }
 
-    pub fn requires_adjecent_wall(&self) -> bool {
+    // Facings a tower can be mounted with, in the order placement rotates through them
+    pub fn orientations(&self) -> &'static [CellDirection] {
         match self {
-            Tower::TrapDoor => true,
-            _ => false,
+            // Lie on the floor the enemies walk over
+            Tower::SpikePit | Tower::TrapDoor | Tower::Portal => &[CellDirection::Down],
+            // Liquids drip down, so they hang from the ceiling or the walls
+            Tower::Oil | Tower::Acid | Tower::Water => {
+                &[CellDirection::Up, CellDirection::Left, CellDirection::Right]
+            }
+            Tower::Harpoon => &[CellDirection::Left, CellDirection::Right],
+            Tower::FrostCannon => &[
+                CellDirection::Down,
+                CellDirection::Left,
+                CellDirection::Right,
+            ],
+            Tower::StunHammer => &[CellDirection::Up, CellDirection::Left, CellDirection::Right],
+            Tower::Piston
+            | Tower::Fan
+            | Tower::Ice
+            | Tower::Tesla
+            | Tower::Flame
+            | Tower::AcidSprayer
+            | Tower::Magnet => &[
+                CellDirection::Down,
+                CellDirection::Left,
+                CellDirection::Up,
+                CellDirection::Right,
+            ],
         }
     }
 
-    pub fn requires_floor_placement(&self) -> bool {
+    // Towers that take up both faces of the piece they are mounted on
+    pub fn spans_both_sides(&self) -> bool {
         match self {
             Tower::TrapDoor => true,
             _ => false,
@@ -200,8 +323,10 @@ impl Tower {
     pub fn fire_sfx(&self) -> Option<Arc<dyn SoundFn>> {
         match self {
             Tower::SpikePit => Some(Arc::new(SoundEffects::spike_fire)),
-            Tower::Tesla => Some(Arc::new(SoundEffects::tesla_fire)),
-            Tower::Piston => Some(Arc::new(SoundEffects::piston_fire)),
+            Tower::Tesla | Tower::Magnet => Some(Arc::new(SoundEffects::tesla_fire)),
+            Tower::Piston | Tower::Harpoon | Tower::StunHammer => {
+                Some(Arc::new(SoundEffects::piston_fire))
+            }
             Tower::Oil => Some(Arc::new(SoundEffects::oil_fire)),
             Tower::Water => Some(Arc::new(SoundEffects::water_fire)),
             _ => None,
@@ -209,6 +334,22 @@ impl Tower {
     }
 }
 
+// The sprite sheets towers are drawn from
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TowerArt {
+    Piston,
+    Fan,
+    SpikePit,
+    Oil,
+    TrapDoor,
+    Ice,
+    Acid,
+    Tesla,
+    Water,
+    Flame,
+    Portal,
+}
+
 #[derive(Component, Default)]
 pub struct TowerCollision {
     pub slowdown: f32,
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::data::{Tower, status_effects::Reaction};
+
+// UI elements a tutorial step can point at
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum TutorialUi {
+    Hotbar(Tower),
+    NextWave,
+    Money,
+    Lives,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialTarget {
+    None,
+    Ui(TutorialUi),
+    // A level cell, counted from the bottom left like the map
+    Cell(UVec2),
+}
+
+// What the player has to do to move on to the next step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TutorialGoal {
+    // Dismissed with the "Got it" button
+    Acknowledge,
+    PlaceTower(Tower),
+    StartWave,
+    Reaction(Reaction),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TutorialStep {
+    pub text: &'static str,
+    pub target: TutorialTarget,
+    pub goal: TutorialGoal,
+}
+
+impl TutorialStep {
+    pub const fn new(text: &'static str, target: TutorialTarget, goal: TutorialGoal) -> Self {
+        Self { text, target, goal }
+    }
+}
+
+pub fn first_level() -> Vec<TutorialStep> {
+    vec![
+        TutorialStep::new(
+            "Enemies walk the path from the green start to the exit. Every one that gets through costs a life.",
+            TutorialTarget::Ui(TutorialUi::Lives),
+            TutorialGoal::Acknowledge,
+        ),
+        TutorialStep::new(
+            "This spike pit came with the level. It is locked in place and can't be sold.",
+            TutorialTarget::Cell(UVec2::new(1, 0)),
+            TutorialGoal::Acknowledge,
+        ),
+        TutorialStep::new(
+            "Pick the Water tower from the hotbar and place it next to the path.",
+            TutorialTarget::Ui(TutorialUi::Hotbar(Tower::Water)),
+            TutorialGoal::PlaceTower(Tower::Water),
+        ),
+        TutorialStep::new(
+            "Wet enemies conduct electricity. Place a Tesla tower further down the path.",
+            TutorialTarget::Ui(TutorialUi::Hotbar(Tower::Tesla)),
+            TutorialGoal::PlaceTower(Tower::Tesla),
+        ),
+        TutorialStep::new(
+            "Press Next Wave when you are ready.",
+            TutorialTarget::Ui(TutorialUi::NextWave),
+            TutorialGoal::StartWave,
+        ),
+        TutorialStep::new(
+            "Shock a soaked enemy to set off Chain Lightning.",
+            TutorialTarget::None,
+            TutorialGoal::Reaction(Reaction::ChainLightning),
+        ),
+    ]
+}
+
+pub fn second_level() -> Vec<TutorialStep> {
+    vec![
+        TutorialStep::new(
+            "Towers cost money. Kills, cleared waves and interest on what you bank pay it back.",
+            TutorialTarget::Ui(TutorialUi::Money),
+            TutorialGoal::Acknowledge,
+        ),
+        TutorialStep::new(
+            "Oil makes enemies flammable. Place an Oil tower.",
+            TutorialTarget::Ui(TutorialUi::Hotbar(Tower::Oil)),
+            TutorialGoal::PlaceTower(Tower::Oil),
+        ),
+        TutorialStep::new(
+            "Now place a Flame tower after it.",
+            TutorialTarget::Ui(TutorialUi::Hotbar(Tower::Flame)),
+            TutorialGoal::PlaceTower(Tower::Flame),
+        ),
+        TutorialStep::new(
+            "Set an oiled enemy on fire to Ignite it.",
+            TutorialTarget::None,
+            TutorialGoal::Reaction(Reaction::Ignite),
+        ),
+    ]
+}
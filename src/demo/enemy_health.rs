@@ -5,6 +5,7 @@ use crate::{
         PlayerState, StatusEffect, Tower, TowerCollision, get_collision,
         projectiles::DamageType,
         stats::{DamageMultiplier, DamageMultiplierAll, Stat},
+        status_effects::StatusEnum,
     },
     demo::enemy_movement::MovementDirection,
     gameplay::shared_systems::Lifetime,
@@ -66,6 +67,10 @@ pub struct TryDamageToEnemy {
     pub strength: usize,
     pub damage_type: DamageType,
     pub enemy: Entity,
+    // Whatever dealt the damage: a tower, its trigger zone or a puddle
+    pub source: Option<Entity>,
+    // Set when the damage is ticking from a status effect
+    pub status: Option<StatusEnum>,
 }
 
 #[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
@@ -73,6 +78,8 @@ pub struct DoDamageToEnemy {
     pub damage: isize,
     pub damage_type: DamageType,
     pub enemy: Entity,
+    pub source: Option<Entity>,
+    pub status: Option<StatusEnum>,
 }
 
 #[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
@@ -148,6 +155,8 @@ fn start_collision_damage_event(
                 damage_type: DamageType::Physical,
                 enemy: enemy_target,
                 strength: 1,
+                source: Some(trigger.target()),
+                status: None,
             });
 
             // Add a collision entity that deals damage on a timer while collision is active
@@ -176,6 +185,8 @@ fn active_tower_collision(
                 damage_type: DamageType::Physical,
                 enemy: entity,
                 strength: 1,
+                source: None,
+                status: None,
             });
         }
     }
@@ -214,6 +225,8 @@ pub fn try_enemy_damage(
             damage: damage as isize,
             damage_type: event.damage_type,
             enemy: event.enemy,
+            source: event.source,
+            status: event.status,
         });
     }
 }
@@ -236,6 +249,18 @@ pub fn do_enemy_damage(
 }
 
 impl EnemyHealth {
+    pub fn current(&self) -> isize {
+        self.current
+    }
+
+    pub fn max(&self) -> isize {
+        self.max
+    }
+
+    pub fn set_current(&mut self, current: isize) {
+        self.current = current.min(self.max);
+    }
+
     pub fn new(health: isize) -> Self {
         Self {
             max: health,
@@ -5,7 +5,10 @@ use crate::{
     AppSystems, PausableSystems,
     data::stats::{MoveSpeed, Stat},
     gameplay::{animation::AnimationFrameQueue, stats::StatSet},
-    level::{components::PathNode, resource::CellDirection},
+    level::{
+        components::{LEVEL_SCALING, PathIndex, PathNode},
+        resource::CellDirection,
+    },
     screens::Screen,
 };
 
@@ -27,6 +30,10 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Component, Default, Clone, Copy, PartialEq, Reflect)]
 pub struct MovementDirection(pub Vec2);
 
+/// How far along the path an enemy has walked, measured in path nodes.
+#[derive(Component, Default, Clone, Copy, PartialEq, Reflect)]
+pub struct PathProgress(pub f32);
+
 /// A bundle that contains the components needed for a basic
 /// kinematic character controller.
 #[derive(Bundle, Clone)]
@@ -46,13 +53,16 @@ fn follow_path(
             &mut MovementDirection,
             &mut GravityScale,
             &Stat<MoveSpeed>,
+            &mut PathProgress,
         ),
         With<EnemyHealth>,
     >,
-    nodes: Query<(&Transform, &PathNode)>,
+    nodes: Query<(&Transform, &PathNode, &PathIndex)>,
 ) {
     // path instructions to walk around in a circle
-    for (enemy_transform, mut movement_direction, mut gravity_scale, speed) in enemies.iter_mut() {
+    for (enemy_transform, mut movement_direction, mut gravity_scale, speed, mut progress) in
+        enemies.iter_mut()
+    {
         let pos = enemy_transform.translation.xy();
 
         let mut nodes_sorted_by_distance = nodes
@@ -60,12 +70,16 @@ fn follow_path(
             .map(|w| {
                 (
                     pos.distance(w.0.translation.xy()),
-                    (w.0, w.1.direction, w.1.prev_direction),
+                    (w.0, w.1.direction, w.1.prev_direction, w.2.0),
                 )
             })
             .collect::<Vec<_>>();
         nodes_sorted_by_distance.sort_by(|w, other| w.0.total_cmp(&other.0));
-        let (_, closest, prev) = nodes_sorted_by_distance[0].1;
+        let (node_transform, closest, prev, index) = nodes_sorted_by_distance[0].1;
+
+        // Offset along the node's exit direction breaks ties between enemies in the same cell
+        let offset = (pos - node_transform.translation.xy()).dot(closest.vec()) / LEVEL_SCALING;
+        progress.0 = index as f32 + offset;
 
         gravity_scale.0 = if speed.current_value() > 0.1
             && (closest == CellDirection::Up || prev == CellDirection::Up)
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    data::{Tower, status_effects::StatusEnum},
+    gameplay::status_effects::common::TryApplyStatus,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TowerSaboteur>();
+    app.add_systems(
+        Update,
+        sabotage_towers
+            .in_set(PausableSystems)
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Enemies that periodically afflict the closest tower in range with a status
+#[derive(Component, Clone, Debug, Reflect)]
+pub struct TowerSaboteur {
+    pub status: StatusEnum,
+    pub strength: usize,
+    pub range: f32,
+    // Only go after this kind of tower, if set
+    pub target: Option<Tower>,
+    pub cooldown: Timer,
+}
+
+impl TowerSaboteur {
+    pub fn new(status: StatusEnum, strength: usize, range: f32, cooldown_secs: f32) -> Self {
+        Self {
+            status,
+            strength,
+            range,
+            target: None,
+            cooldown: Timer::from_seconds(cooldown_secs, TimerMode::Repeating),
+        }
+    }
+
+    pub fn targeting(mut self, tower: Tower) -> Self {
+        self.target = Some(tower);
+        self
+    }
+}
+
+fn sabotage_towers(
+    mut saboteurs: Query<(Entity, &GlobalTransform, &mut TowerSaboteur)>,
+    towers: Query<(Entity, &GlobalTransform, &Tower)>,
+    mut statuses: EventWriter<TryApplyStatus>,
+    time: Res<Time>,
+) {
+    for (saboteur_entity, saboteur_pos, mut saboteur) in saboteurs.iter_mut() {
+        saboteur.cooldown.tick(time.delta());
+        if !saboteur.cooldown.just_finished() {
+            continue;
+        }
+
+        let origin = saboteur_pos.translation().xy();
+        let closest = towers
+            .iter()
+            .filter(|(_, _, tower)| saboteur.target.is_none_or(|w| w == **tower))
+            .map(|(e, pos, _)| (e, pos.translation().xy().distance(origin)))
+            .filter(|(_, distance)| *distance < saboteur.range)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((tower, _)) = closest {
+            statuses.write(TryApplyStatus {
+                status: saboteur.status,
+                enemy: tower,
+                strength: saboteur.strength,
+                source: Some(saboteur_entity),
+            });
+        }
+    }
+}
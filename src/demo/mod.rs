@@ -12,6 +12,7 @@ use bevy::prelude::*;
 mod animation;
 pub mod enemy_health;
 pub mod enemy_movement;
+pub mod enemy_sabotage;
 mod movement;
 pub mod player;
 
@@ -22,6 +23,7 @@ pub(super) fn plugin(app: &mut App) {
         PhysicsDebugPlugin::default(),
         enemy_movement::plugin,
         enemy_health::plugin,
+        enemy_sabotage::plugin,
         animation::plugin,
         movement::plugin,
         player::plugin,
@@ -5,6 +5,7 @@ use crate::demo::{
     animation::PlayerAnimation,
     movement::{MovementController, ScreenWrap},
 };
+use crate::input::{Action, Actions};
 use crate::prelude::*;
 use avian2d::prelude::{Collider, CollisionEventsEnabled, LockedAxes, RigidBody};
 use bevy::prelude::*;
@@ -65,21 +66,21 @@ pub fn player(
 struct Player;
 
 fn record_player_directional_input(
-    input: Res<ButtonInput<KeyCode>>,
+    actions: Actions,
     mut controller_query: Query<&mut MovementController, With<Player>>,
 ) {
     // Collect directional input.
     let mut intent = Vec2::ZERO;
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+    if actions.pressed(Action::Up) {
         intent.y += 1.0;
     }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+    if actions.pressed(Action::Down) {
         intent.y -= 1.0;
     }
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if actions.pressed(Action::Left) {
         intent.x -= 1.0;
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if actions.pressed(Action::Right) {
         intent.x += 1.0;
     }
 
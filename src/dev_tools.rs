@@ -2,14 +2,16 @@
 
 use avian2d::debug_render::PhysicsGizmos;
 use avian2d::prelude::PhysicsDebugPlugin;
-use bevy::{
-    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
-    ui::UiDebugOptions,
-};
+use bevy::{dev_tools::states::log_transitions, prelude::*, ui::UiDebugOptions};
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
 use crate::data::PlayerState;
+use crate::demo::enemy_health::EnemyHealth;
+use crate::gameplay::combat_log::CombatLog;
+use crate::input::{Action, action_just_pressed};
+use crate::level::components::LEVEL_SCALING;
 use crate::level::resource::LevelSelect;
+use crate::persistence;
 use crate::{data::PointerInteractionState, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
@@ -20,20 +22,40 @@ pub(super) fn plugin(app: &mut App) {
     // Toggle the debug overlay for UI.
     app.add_systems(
         Update,
-        toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
+        toggle_debug_ui.run_if(action_just_pressed(Action::DebugUi)),
     );
 
-    app.add_systems(Update, add_1k.run_if(input_just_pressed(KeyCode::KeyM)));
-    app.add_systems(Update, next_level.run_if(input_just_pressed(KeyCode::KeyN)));
+    app.add_systems(Update, add_1k.run_if(action_just_pressed(Action::AddMoney)));
+    app.add_systems(
+        Update,
+        next_level.run_if(action_just_pressed(Action::SkipLevel)),
+    );
     app.add_systems(Startup, on_startup);
 
+    // Combat log overlay, filtered to whichever enemy is under the cursor.
+    app.init_resource::<HoveredEnemy>();
+    app.add_systems(
+        Update,
+        toggle_combat_log.run_if(action_just_pressed(Action::CombatLog)),
+    );
+    app.add_systems(
+        Update,
+        export_combat_log.run_if(action_just_pressed(Action::ExportCombatLog)),
+    );
+    app.add_systems(
+        Update,
+        (update_hovered_enemy, update_combat_log_overlay)
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+
     app.add_plugins(EguiPlugin {
         enable_multipass_for_primary_context: true,
     });
     app.add_plugins(WorldInspectorPlugin::new());
 }
 
-const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const COMBAT_LOG_LINES: usize = 20;
 
 fn on_startup(mut store: ResMut<GizmoConfigStore>) {
     store.config_mut::<PhysicsGizmos>().0.enabled = false
@@ -54,3 +76,114 @@ fn next_level(mut level_select: ResMut<LevelSelect>, mut next_screen: ResMut<Nex
     level_select.0 += 1;
     next_screen.set(Screen::LevelTransition);
 }
+
+#[derive(Resource, Default)]
+struct HoveredEnemy(Option<Entity>);
+
+#[derive(Component)]
+struct CombatLogOverlay;
+
+fn update_hovered_enemy(
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    enemies: Query<(Entity, &GlobalTransform), With<EnemyHealth>>,
+    mut hovered: ResMut<HoveredEnemy>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    let Some(game_cursor_position) = window
+        .cursor_position()
+        .and_then(|w| camera.viewport_to_world_2d(camera_transform, w).ok())
+    else {
+        return;
+    };
+
+    // Keep showing the last enemy after it dies or the cursor leaves it
+    if let Some((entity, _)) = enemies
+        .iter()
+        .map(|(e, t)| (e, t.translation().xy().distance(game_cursor_position)))
+        .filter(|(_, distance)| *distance < LEVEL_SCALING / 2.)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    {
+        hovered.0 = Some(entity);
+    }
+}
+
+fn toggle_combat_log(
+    overlays: Query<Entity, With<CombatLogOverlay>>,
+    screen: Res<State<Screen>>,
+    mut commands: Commands,
+) {
+    if let Ok(overlay) = overlays.single() {
+        commands.entity(overlay).despawn();
+        return;
+    }
+    if *screen.get() != Screen::Gameplay {
+        return;
+    }
+    commands.spawn((
+        Name::new("Combat Log Overlay"),
+        CombatLogOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            bottom: Val::Px(8.0),
+            padding: UiRect::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.7)),
+        Text::default(),
+        TextFont::from_font_size(12.0),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn update_combat_log_overlay(
+    log: Res<CombatLog>,
+    hovered: Res<HoveredEnemy>,
+    mut overlays: Query<&mut Text, With<CombatLogOverlay>>,
+) {
+    let Ok(mut text) = overlays.single_mut() else {
+        return;
+    };
+
+    let lines = match hovered.0 {
+        Some(enemy) => log
+            .for_enemy(enemy)
+            .rev()
+            .take(COMBAT_LOG_LINES)
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>(),
+        None => log
+            .entries()
+            .rev()
+            .take(COMBAT_LOG_LINES)
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>(),
+    };
+    let header = match hovered.0 {
+        Some(enemy) => format!("Combat log: {enemy}"),
+        None => "Combat log: hover an enemy".to_string(),
+    };
+
+    text.0 = std::iter::once(header)
+        .chain(lines.into_iter().rev())
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+fn export_combat_log(log: Res<CombatLog>) {
+    for (file_name, text) in [
+        ("combat_log.txt", log.to_text()),
+        ("combat_log.json", log.to_json()),
+    ] {
+        match persistence::export(file_name, &text) {
+            Ok(location) => info!("Exported combat log to {location}"),
+            Err(e) => warn!("Failed to export {file_name}: {e}"),
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    PausableSystems,
+    data::{
+        PlayerState, Tower,
+        placement::{PlacedTower, PlacementContext, evaluate},
+    },
+    demo::enemy_health::EnemyHealth,
+    gameplay::{
+        build_queue::BuildQueue,
+        level_setup::Preplaced,
+        messages::DisplayFlashMessage,
+        tower_placement::{PlacementRules, spawn_tower},
+        towers::targeting::TargetPriority,
+        wave_manager::WaveManager,
+    },
+    input::{Action, Actions},
+    level::{components::Adjacent, resource::CellDirection},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ActionHistory>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_history);
+    app.add_systems(
+        Update,
+        (lock_history_during_waves, undo_action, redo_action)
+            .chain()
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Everything needed to put a tower back the way it was
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+pub struct TowerSnapshot {
+    pub tower: Tower,
+    pub piece: Entity,
+    pub orientation: CellDirection,
+    pub fan_direction: Option<CellDirection>,
+    pub priority: TargetPriority,
+    // Undoing the sale of a free tower must not turn it into a refundable one
+    pub preplaced: Option<Preplaced>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Reflect)]
+pub enum TowerAction {
+    Placed(TowerSnapshot, i32),
+    Sold(TowerSnapshot, i32),
+}
+
+impl TowerAction {
+    // Undoing an action is performing its opposite
+    pub fn inverse(&self) -> Self {
+        match *self {
+            TowerAction::Placed(snapshot, price) => TowerAction::Sold(snapshot, price),
+            TowerAction::Sold(snapshot, refund) => TowerAction::Placed(snapshot, refund),
+        }
+    }
+}
+
+// Placements and sells made during the current wave break
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ActionHistory {
+    done: Vec<TowerAction>,
+    undone: Vec<TowerAction>,
+}
+
+impl ActionHistory {
+    pub fn record(&mut self, action: TowerAction) {
+        self.done.push(action);
+        self.undone.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty() && self.undone.is_empty()
+    }
+}
+
+fn clear_history(mut history: ResMut<ActionHistory>) {
+    history.clear();
+}
+
+// Once enemies are on the field the break's actions are final
+fn lock_history_during_waves(
+    wave_manager: Res<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut history: ResMut<ActionHistory>,
+) {
+    if wave_active(&wave_manager, &enemies) && !history.is_empty() {
+        history.clear();
+    }
+}
+
+// Stragglers of a finished wave still count, the break starts once they are gone
+fn wave_active(wave_manager: &WaveManager, enemies: &Query<(), With<EnemyHealth>>) -> bool {
+    wave_manager.current_wave.is_some() || !enemies.is_empty()
+}
+
+fn undo_action(
+    actions: Actions,
+    wave_manager: Res<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut history: ResMut<ActionHistory>,
+    mut replay: Replay,
+) {
+    if !actions.pressed(Action::Command)
+        || actions.pressed(Action::Modifier)
+        || !actions.just_pressed(Action::Undo)
+    {
+        return;
+    }
+    if wave_active(&wave_manager, &enemies) {
+        replay
+            .commands
+            .trigger(DisplayFlashMessage::new("Can't undo during a wave"));
+        return;
+    }
+    let Some(action) = history.done.pop() else {
+        return;
+    };
+
+    if replay.perform(action.inverse()) {
+        history.undone.push(action);
+    } else {
+        history.done.push(action);
+    }
+}
+
+fn redo_action(
+    actions: Actions,
+    wave_manager: Res<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut history: ResMut<ActionHistory>,
+    mut replay: Replay,
+) {
+    let redo = actions.just_pressed(Action::Redo)
+        || (actions.pressed(Action::Modifier) && actions.just_pressed(Action::Undo));
+    if !actions.pressed(Action::Command) || !redo {
+        return;
+    }
+    if wave_active(&wave_manager, &enemies) {
+        replay
+            .commands
+            .trigger(DisplayFlashMessage::new("Can't redo during a wave"));
+        return;
+    }
+    let Some(action) = history.undone.pop() else {
+        return;
+    };
+
+    if replay.perform(action) {
+        history.done.push(action);
+    } else {
+        history.undone.push(action);
+    }
+}
+
+// Everything needed to apply a recorded action to the level again
+#[derive(SystemParam)]
+struct Replay<'w, 's> {
+    player_state: ResMut<'w, PlayerState>,
+    rules: Res<'w, PlacementRules>,
+    queue: Res<'w, BuildQueue>,
+    towers: Query<'w, 's, (Entity, &'static ChildOf, &'static Tower)>,
+    pieces: Query<'w, 's, &'static Adjacent>,
+    commands: Commands<'w, 's>,
+}
+
+impl Replay<'_, '_> {
+    // Applies an action without recording it, false if the level no longer allows it
+    fn perform(&mut self, action: TowerAction) -> bool {
+        match action {
+            TowerAction::Placed(snapshot, price) => {
+                let Ok(piece) = self.pieces.get(snapshot.piece) else {
+                    return false;
+                };
+                if self.queue.is_reserved(snapshot.piece) {
+                    self.commands
+                        .trigger(DisplayFlashMessage::new("This spot is reserved"));
+                    return false;
+                }
+                let placed: Vec<_> = self
+                    .towers
+                    .iter()
+                    .filter_map(|(_, ChildOf(parent), tower)| {
+                        self.pieces.get(*parent).ok().map(|piece| PlacedTower {
+                            tower: *tower,
+                            piece: *piece,
+                        })
+                    })
+                    .collect();
+                // Money is checked below, undoing a sell costs the refund rather than the price
+                let verdict = evaluate(
+                    &self.rules.0,
+                    &PlacementContext {
+                        tower: snapshot.tower,
+                        piece: *piece,
+                        orientation: snapshot.orientation,
+                        money: i32::MAX,
+                        placed: &placed,
+                    },
+                );
+                if let Some(reason) = verdict.reason() {
+                    self.commands.trigger(DisplayFlashMessage::new(reason));
+                    return false;
+                }
+                if !self.player_state.can_afford(price) {
+                    self.commands
+                        .trigger(DisplayFlashMessage::new("Insufficient funds"));
+                    return false;
+                }
+                self.player_state.money -= price;
+                spawn_tower(&mut self.commands, snapshot);
+                true
+            }
+            TowerAction::Sold(snapshot, refund) => {
+                let Some((tower_entity, _, _)) = self.towers.iter().find(|(_, w, tower)| {
+                    w.parent() == snapshot.piece && **tower == snapshot.tower
+                }) else {
+                    return false;
+                };
+                self.player_state.money += refund;
+                self.commands.entity(tower_entity).despawn();
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::level::components::{ExactPosition, GeneralPosition};
+
+    fn snapshot(piece: Entity) -> TowerSnapshot {
+        TowerSnapshot {
+            tower: Tower::Piston,
+            piece,
+            orientation: CellDirection::Down,
+            fan_direction: None,
+            priority: TargetPriority::default(),
+            preplaced: None,
+        }
+    }
+
+    #[test]
+    fn inverse_swaps_placing_and_selling() {
+        let snapshot = snapshot(Entity::PLACEHOLDER);
+        let placed = TowerAction::Placed(snapshot, 50);
+        assert_eq!(placed.inverse(), TowerAction::Sold(snapshot, 50));
+        assert_eq!(placed.inverse().inverse(), placed);
+    }
+
+    #[test]
+    fn recording_clears_the_redo_stack() {
+        let snapshot = snapshot(Entity::PLACEHOLDER);
+        let mut history = ActionHistory::default();
+        history.record(TowerAction::Placed(snapshot, 50));
+        history.undone.push(TowerAction::Sold(snapshot, 50));
+        history.record(TowerAction::Placed(snapshot, 50));
+
+        assert_eq!(history.done.len(), 2);
+        assert!(history.undone.is_empty());
+    }
+
+    fn world(money: i32) -> (World, Entity) {
+        let mut world = World::new();
+        world.insert_resource(PlayerState { money, ..default() });
+        world.init_resource::<PlacementRules>();
+        world.init_resource::<BuildQueue>();
+        let piece = world
+            .spawn(Adjacent::new(
+                1,
+                1,
+                GeneralPosition::UpDown,
+                ExactPosition::Floor,
+            ))
+            .id();
+        (world, piece)
+    }
+
+    fn perform(world: &mut World, action: TowerAction) -> bool {
+        world
+            .run_system_once(move |mut replay: Replay| replay.perform(action))
+            .unwrap()
+    }
+
+    #[test]
+    fn undoing_a_placement_refunds_its_price() {
+        let (mut world, piece) = world(100);
+        world.spawn((Tower::Piston, ChildOf(piece)));
+
+        let placed = TowerAction::Placed(snapshot(piece), 50);
+        assert!(perform(&mut world, placed.inverse()));
+        assert_eq!(world.resource::<PlayerState>().money, 150);
+        assert!(world.query::<&Tower>().iter(&world).next().is_none());
+    }
+
+    #[test]
+    fn undoing_a_sale_charges_the_refund() {
+        let (mut world, piece) = world(100);
+
+        // Buying it back costs what the sale paid out, not the list price
+        let sold = TowerAction::Sold(snapshot(piece), 40);
+        assert!(perform(&mut world, sold.inverse()));
+        assert_eq!(world.resource::<PlayerState>().money, 60);
+    }
+
+    #[test]
+    fn replays_are_denied_without_funds() {
+        let (mut world, piece) = world(10);
+
+        let placed = TowerAction::Placed(snapshot(piece), 50);
+        assert!(!perform(&mut world, placed));
+        assert_eq!(world.resource::<PlayerState>().money, 10);
+    }
+
+    #[test]
+    fn replays_respect_the_placement_rules() {
+        let (mut world, piece) = world(100);
+        world.spawn((Tower::Fan, ChildOf(piece)));
+
+        let placed = TowerAction::Placed(snapshot(piece), 50);
+        assert!(!perform(&mut world, placed));
+        assert_eq!(world.resource::<PlayerState>().money, 100);
+    }
+}
@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+
+use bevy::{
+    color::palettes::tailwind,
+    input::{
+        ButtonState, InputSystem,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::TowerSprites,
+    data::{
+        PlayerState, Tower,
+        levels::LevelData,
+        placement::{PlacedTower, PlacementContext, evaluate},
+    },
+    gameplay::{
+        action_history::{ActionHistory, TowerAction, TowerSnapshot},
+        build_queue::{BuildQueue, queue_build},
+        level_setup::Preplaced,
+        messages::DisplayFlashMessage,
+        tower_placement::{PlacementRules, spawn_tower},
+        towers::{directional::FireDirection, targeting::TargetPriority},
+    },
+    level::{
+        components::{Adjacent, AdjacentId, ExactPosition},
+        resource::{CellDirection, CurrentLoadedLevel},
+    },
+    persistence,
+    prelude::*,
+    theme::{handles::LABEL_FONT, widget},
+};
+
+const BLUEPRINTS_KEY: &str = "blueprints";
+const MAX_NAME_LENGTH: usize = 24;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(persistence::load::<Blueprints>(BLUEPRINTS_KEY).unwrap_or_default());
+    app.init_resource::<SelectedBlueprint>();
+    app.init_resource::<RenamingBlueprint>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (reset_blueprint_selection, spawn_blueprint_buttons),
+    );
+    // Runs before everything else reads input, so typing doesn't trigger hotkeys
+    app.add_systems(
+        PreUpdate,
+        type_blueprint_name
+            .after(InputSystem)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_blueprint_label
+            .run_if(
+                resource_changed::<Blueprints>
+                    .or(resource_changed::<SelectedBlueprint>)
+                    .or(resource_changed::<RenamingBlueprint>)
+                    .or(resource_changed::<CurrentLoadedLevel>),
+            )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlueprintTower {
+    pub tower: Tower,
+    pub piece: AdjacentId,
+    pub exact_position: ExactPosition,
+    pub orientation: CellDirection,
+    pub fan_direction: Option<CellDirection>,
+    // Towers can't be upgraded yet, this keeps saved layouts valid once they can
+    #[serde(default)]
+    pub upgrade_level: u32,
+}
+
+impl BlueprintTower {
+    pub fn new(
+        tower: Tower,
+        piece: &Adjacent,
+        orientation: CellDirection,
+        fire_direction: Option<&FireDirection>,
+    ) -> Self {
+        Self {
+            tower,
+            piece: piece.id,
+            exact_position: piece.exact_position,
+            orientation,
+            fan_direction: fire_direction.filter(|_| tower == Tower::Fan).map(|w| w.0),
+            upgrade_level: 0,
+        }
+    }
+
+    // Pieces are respawned with every level load, so they are matched by position
+    pub fn find_piece<'a>(
+        &self,
+        mut pieces: impl Iterator<Item = (Entity, &'a Adjacent)>,
+    ) -> Option<(Entity, &'a Adjacent)> {
+        pieces.find(|(_, w)| w.id == self.piece && w.exact_position == self.exact_position)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Blueprint {
+    pub name: String,
+    pub towers: Vec<BlueprintTower>,
+}
+
+// Saved tower layouts, keyed by level index
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Blueprints(pub HashMap<usize, Vec<Blueprint>>);
+
+impl Blueprints {
+    // Forgets every saved layout, on disk as well
+    pub fn clear(&mut self) {
+        self.0.clear();
+        persistence::remove(BLUEPRINTS_KEY);
+    }
+
+    // The index wraps, so it stays valid as layouts are added
+    pub fn get(&self, level: usize, index: usize) -> Option<&Blueprint> {
+        let layouts = self.0.get(&level)?;
+        layouts.get(index % layouts.len().max(1))
+    }
+
+    pub fn get_mut(&mut self, level: usize, index: usize) -> Option<&mut Blueprint> {
+        let layouts = self.0.get_mut(&level)?;
+        let count = layouts.len().max(1);
+        layouts.get_mut(index % count)
+    }
+
+    pub fn remove(&mut self, level: usize, index: usize) -> Option<Blueprint> {
+        let layouts = self.0.get_mut(&level)?;
+        if layouts.is_empty() {
+            return None;
+        }
+        let removed = layouts.remove(index % layouts.len());
+        if layouts.is_empty() {
+            self.0.remove(&level);
+        }
+        Some(removed)
+    }
+}
+
+// Which of the level's layouts "Apply Layout" uses
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct SelectedBlueprint(pub usize);
+
+// The name being typed for the selected layout, None when not renaming
+#[derive(Resource, Debug, Default, Clone)]
+struct RenamingBlueprint(Option<String>);
+
+#[derive(Component, Debug, Clone, Copy)]
+struct BlueprintLabel;
+
+fn reset_blueprint_selection(
+    mut selected: ResMut<SelectedBlueprint>,
+    mut renaming: ResMut<RenamingBlueprint>,
+) {
+    selected.0 = 0;
+    renaming.0 = None;
+}
+
+fn spawn_blueprint_buttons(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Blueprint Buttons"),
+        StateScoped(Screen::Gameplay),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(64.0),
+            right: Val::Px(16.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        children![
+            widget::button_medium("Save Layout", save_blueprint),
+            widget::button_medium("Next Layout", select_next_blueprint),
+            widget::button_medium("Rename Layout", rename_blueprint),
+            widget::button_medium("Delete Layout", delete_blueprint),
+            widget::button_medium("Apply Layout", apply_blueprint),
+            (
+                BlueprintLabel,
+                Text::default(),
+                TextFont::from_font(LABEL_FONT).with_font_size(16.0),
+                TextColor(tailwind::INDIGO_950.into()),
+                Pickable::IGNORE,
+            ),
+        ],
+    ));
+}
+
+fn update_blueprint_label(
+    blueprints: Res<Blueprints>,
+    selected: Res<SelectedBlueprint>,
+    renaming: Res<RenamingBlueprint>,
+    level: Res<CurrentLoadedLevel>,
+    mut labels: Query<&mut Text, With<BlueprintLabel>>,
+) {
+    let text = match (&renaming.0, blueprints.get(level.0, selected.0)) {
+        (Some(name), _) => format!("Name: {name}_ (Enter to keep)"),
+        (None, Some(blueprint)) => format!("Layout: {}", blueprint.name),
+        (None, None) => "No saved layouts".to_string(),
+    };
+    for mut label in labels.iter_mut() {
+        label.0 = text.clone();
+    }
+}
+
+fn select_next_blueprint(
+    _: Trigger<Pointer<Click>>,
+    blueprints: Res<Blueprints>,
+    level: Res<CurrentLoadedLevel>,
+    mut selected: ResMut<SelectedBlueprint>,
+    mut renaming: ResMut<RenamingBlueprint>,
+    mut commands: Commands,
+) {
+    let count = blueprints.0.get(&level.0).map_or(0, Vec::len);
+    if count == 0 {
+        commands.trigger(DisplayFlashMessage::new("No saved layout for this level"));
+        return;
+    }
+    selected.0 = (selected.0 % count + 1) % count;
+    renaming.0 = None;
+}
+
+fn rename_blueprint(
+    _: Trigger<Pointer<Click>>,
+    blueprints: Res<Blueprints>,
+    selected: Res<SelectedBlueprint>,
+    level: Res<CurrentLoadedLevel>,
+    mut renaming: ResMut<RenamingBlueprint>,
+    mut commands: Commands,
+) {
+    let Some(blueprint) = blueprints.get(level.0, selected.0) else {
+        commands.trigger(DisplayFlashMessage::new("No saved layout for this level"));
+        return;
+    };
+    renaming.0 = Some(blueprint.name.clone());
+}
+
+fn delete_blueprint(
+    _: Trigger<Pointer<Click>>,
+    level: Res<CurrentLoadedLevel>,
+    mut blueprints: ResMut<Blueprints>,
+    mut selected: ResMut<SelectedBlueprint>,
+    mut renaming: ResMut<RenamingBlueprint>,
+    mut commands: Commands,
+) {
+    let Some(removed) = blueprints.remove(level.0, selected.0) else {
+        commands.trigger(DisplayFlashMessage::new("No saved layout for this level"));
+        return;
+    };
+    selected.0 = selected.0.saturating_sub(1);
+    renaming.0 = None;
+    persistence::save(BLUEPRINTS_KEY, &*blueprints);
+    commands.trigger(DisplayFlashMessage::new(format!(
+        "Deleted {}",
+        removed.name
+    )));
+}
+
+// Enter keeps the typed name, Escape keeps the old one
+fn type_blueprint_name(
+    mut typed: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut renaming: ResMut<RenamingBlueprint>,
+    mut blueprints: ResMut<Blueprints>,
+    selected: Res<SelectedBlueprint>,
+    level: Res<CurrentLoadedLevel>,
+    mut commands: Commands,
+) {
+    if renaming.0.is_none() {
+        typed.clear();
+        return;
+    }
+    keys.clear();
+
+    let mut name = renaming.0.clone().unwrap_or_default();
+    let mut finished = None;
+    for event in typed.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => finished = Some(true),
+            Key::Escape => finished = Some(false),
+            Key::Backspace => {
+                name.pop();
+            }
+            Key::Space => name.push(' '),
+            Key::Character(text) => name.push_str(text),
+            _ => {}
+        }
+        if finished.is_some() {
+            break;
+        }
+    }
+    name = name.chars().take(MAX_NAME_LENGTH).collect();
+
+    match finished {
+        None => {
+            if renaming.0.as_ref() != Some(&name) {
+                renaming.0 = Some(name);
+            }
+        }
+        Some(keep) => {
+            renaming.0 = None;
+            let name = name.trim();
+            if !keep || name.is_empty() {
+                return;
+            }
+            if let Some(blueprint) = blueprints.get_mut(level.0, selected.0) {
+                blueprint.name = name.to_string();
+                persistence::save(BLUEPRINTS_KEY, &*blueprints);
+                commands.trigger(DisplayFlashMessage::new(format!(
+                    "Renamed layout to {name}"
+                )));
+            }
+        }
+    }
+}
+
+fn save_blueprint(
+    _: Trigger<Pointer<Click>>,
+    // The level puts its own towers back, they don't belong in a layout
+    towers: Query<(&Tower, &ChildOf, &CellDirection, Option<&FireDirection>), Without<Preplaced>>,
+    pieces: Query<&Adjacent>,
+    level: Res<CurrentLoadedLevel>,
+    mut blueprints: ResMut<Blueprints>,
+    mut selected: ResMut<SelectedBlueprint>,
+    mut renaming: ResMut<RenamingBlueprint>,
+    mut commands: Commands,
+) {
+    let towers: Vec<_> = towers
+        .iter()
+        .filter_map(|(tower, ChildOf(parent), orientation, fire_direction)| {
+            let piece = pieces.get(*parent).ok()?;
+            Some(BlueprintTower::new(
+                *tower,
+                piece,
+                *orientation,
+                fire_direction,
+            ))
+        })
+        .collect();
+
+    if towers.is_empty() {
+        commands.trigger(DisplayFlashMessage::new("No towers to save"));
+        return;
+    }
+
+    // Every save is kept as its own layout, the newest one gets selected
+    let count = towers.len();
+    let layouts = blueprints.0.entry(level.0).or_default();
+    let name = format!("Level {} layout {}", level.0 + 1, layouts.len() + 1);
+    layouts.push(Blueprint {
+        name: name.clone(),
+        towers,
+    });
+    selected.0 = layouts.len() - 1;
+    persistence::save(BLUEPRINTS_KEY, &*blueprints);
+    commands.trigger(DisplayFlashMessage::new(format!(
+        "Saved {name} with {count} towers, type a name for it"
+    )));
+    renaming.0 = Some(name);
+}
+
+// Builds what can be paid for right away and queues the rest
+fn apply_blueprint(
+    _: Trigger<Pointer<Click>>,
+    blueprints: Res<Blueprints>,
+    selected: Res<SelectedBlueprint>,
+    level: Res<CurrentLoadedLevel>,
+    level_data: Res<LevelData>,
+    rules: Res<PlacementRules>,
+    pieces: Query<(Entity, &Adjacent)>,
+    towers: Query<(&ChildOf, &Tower)>,
+    sprites: Res<TowerSprites>,
+    mut player_state: ResMut<PlayerState>,
+    mut history: ResMut<ActionHistory>,
+    mut queue: ResMut<BuildQueue>,
+    mut commands: Commands,
+) {
+    let Some(blueprint) = blueprints.get(level.0, selected.0) else {
+        commands.trigger(DisplayFlashMessage::new("No saved layout for this level"));
+        return;
+    };
+
+    let mut placed: Vec<_> = towers
+        .iter()
+        .filter_map(|(ChildOf(parent), tower)| {
+            pieces.get(*parent).ok().map(|(_, piece)| PlacedTower {
+                tower: *tower,
+                piece: *piece,
+            })
+        })
+        .collect();
+
+    let setup = level_data.setup(level.0);
+    let (mut built, mut queued) = (0, 0);
+    for entry in blueprint.towers.iter().filter(|w| setup.allows(w.tower)) {
+        let Some((piece_entity, piece)) = entry.find_piece(pieces.iter()) else {
+            continue;
+        };
+        if queue.is_reserved(piece_entity) {
+            continue;
+        }
+
+        // Money is handled below, only the layout itself has to be legal
+        let verdict = evaluate(
+            &rules.0,
+            &PlacementContext {
+                tower: entry.tower,
+                piece: *piece,
+                orientation: entry.orientation,
+                money: i32::MAX,
+                placed: &placed,
+            },
+        );
+        if !verdict.is_allowed() {
+            continue;
+        }
+        placed.push(PlacedTower {
+            tower: entry.tower,
+            piece: *piece,
+        });
+
+        let snapshot = TowerSnapshot {
+            tower: entry.tower,
+            piece: piece_entity,
+            orientation: entry.orientation,
+            fan_direction: entry.fan_direction,
+            priority: TargetPriority::default(),
+            preplaced: None,
+        };
+        let price = entry.tower.price();
+        if player_state.can_afford(price) {
+            player_state.money -= price;
+            spawn_tower(&mut commands, snapshot);
+            history.record(TowerAction::Placed(snapshot, price));
+            built += 1;
+        } else {
+            queue_build(&mut commands, &mut queue, &sprites, snapshot);
+            queued += 1;
+        }
+    }
+
+    commands.trigger(DisplayFlashMessage::new(format!(
+        "Built {built} towers, {queued} queued"
+    )));
+}
@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{
+    PausableSystems,
+    assets::TowerSprites,
+    data::{
+        PlayerState, Tower,
+        placement::{PlacedTower, PlacementContext, evaluate},
+    },
+    gameplay::{
+        action_history::{ActionHistory, TowerAction, TowerSnapshot},
+        messages::DisplayFlashMessage,
+        tower_placement::{PlacementRules, spawn_tower},
+    },
+    level::components::Adjacent,
+    screens::Screen,
+    theme::{handles::LABEL_FONT, widget},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<BuildQueue>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (clear_build_queue, spawn_build_queue_list),
+    );
+    app.add_systems(
+        Update,
+        build_queued_towers
+            .run_if(resource_changed::<PlayerState>)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        update_build_queue_list
+            .run_if(resource_changed::<BuildQueue>)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct QueuedBuild {
+    pub snapshot: TowerSnapshot,
+    pub ghost: Entity,
+}
+
+// Towers reserved by the player, built in order as money comes in
+#[derive(Resource, Debug, Default, Clone)]
+pub struct BuildQueue(pub VecDeque<QueuedBuild>);
+
+impl BuildQueue {
+    pub fn is_reserved(&self, piece: Entity) -> bool {
+        self.0.iter().any(|w| w.snapshot.piece == piece)
+    }
+
+    // Drops a reservation, nothing was paid so nothing is refunded
+    pub fn cancel(&mut self, commands: &mut Commands, ghost: Entity) -> Option<QueuedBuild> {
+        let index = self.0.iter().position(|w| w.ghost == ghost)?;
+        let cancelled = self.0.remove(index)?;
+        commands.entity(cancelled.ghost).try_despawn();
+        Some(cancelled)
+    }
+}
+
+// A see-through stand-in for a tower that hasn't been paid for yet
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BuildGhost;
+
+#[derive(Component, Debug, Clone, Copy)]
+struct BuildQueueList;
+
+pub fn queue_build(
+    commands: &mut Commands,
+    queue: &mut BuildQueue,
+    sprites: &TowerSprites,
+    snapshot: TowerSnapshot,
+) {
+    if queue.is_reserved(snapshot.piece) {
+        return;
+    }
+    let (mut sprite, animation) = sprites.tower_bundle(&snapshot.tower, &snapshot.orientation);
+    sprite.color = sprite.color.with_alpha(0.35);
+    let ghost = commands
+        .spawn((
+            Name::new("Build Ghost"),
+            BuildGhost,
+            sprite,
+            animation,
+            snapshot.orientation.sprite_offset(&snapshot.tower),
+            ChildOf(snapshot.piece),
+        ))
+        .id();
+    queue.0.push_back(QueuedBuild { snapshot, ghost });
+}
+
+fn clear_build_queue(mut queue: ResMut<BuildQueue>) {
+    queue.0.clear();
+}
+
+fn build_queued_towers(
+    mut queue: ResMut<BuildQueue>,
+    mut player_state: ResMut<PlayerState>,
+    mut history: ResMut<ActionHistory>,
+    rules: Res<PlacementRules>,
+    towers: Query<(&ChildOf, &Tower)>,
+    pieces: Query<&Adjacent>,
+    mut commands: Commands,
+) {
+    let mut placed: Vec<_> = towers
+        .iter()
+        .filter_map(|(ChildOf(parent), tower)| {
+            pieces.get(*parent).ok().map(|piece| PlacedTower {
+                tower: *tower,
+                piece: *piece,
+            })
+        })
+        .collect();
+
+    while let Some(next) = queue.0.front().copied() {
+        let tower = next.snapshot.tower;
+        let Ok(piece) = pieces.get(next.snapshot.piece).copied() else {
+            commands.entity(next.ghost).try_despawn();
+            queue.0.pop_front();
+            continue;
+        };
+
+        // The level may have changed since the spot was reserved
+        let verdict = evaluate(
+            &rules.0,
+            &PlacementContext {
+                tower,
+                piece,
+                orientation: next.snapshot.orientation,
+                money: i32::MAX,
+                placed: &placed,
+            },
+        );
+        if let Some(reason) = verdict.reason() {
+            commands.entity(next.ghost).try_despawn();
+            queue.0.pop_front();
+            commands.trigger(DisplayFlashMessage::new(format!(
+                "Queued {} dropped: {reason}",
+                tower.name()
+            )));
+            continue;
+        }
+
+        let price = tower.price();
+        if !player_state.can_afford(price) {
+            break;
+        }
+        placed.push(PlacedTower { tower, piece });
+        player_state.money -= price;
+        commands.entity(next.ghost).try_despawn();
+        spawn_tower(&mut commands, next.snapshot);
+        history.record(TowerAction::Placed(next.snapshot, price));
+        queue.0.pop_front();
+    }
+}
+
+fn spawn_build_queue_list(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Build Queue"),
+        StateScoped(Screen::Gameplay),
+        BuildQueueList,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(64.0),
+            left: Val::Px(16.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(tailwind::INDIGO_300.with_alpha(0.8).into()),
+        Pickable::IGNORE,
+    ));
+}
+
+fn update_build_queue_list(
+    queue: Res<BuildQueue>,
+    mut lists: Query<(Entity, &mut Node), With<BuildQueueList>>,
+    mut commands: Commands,
+) {
+    for (entity, mut node) in lists.iter_mut() {
+        node.display = if queue.0.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .with_children(|list| {
+                list.spawn(widget::ui_font("Build queue"));
+                for (i, queued) in queue.0.iter().enumerate() {
+                    let tower = queued.snapshot.tower;
+                    list.spawn((
+                        Text(format!("{}. {} ({})", i + 1, tower.name(), tower.price())),
+                        TextFont::from_font(LABEL_FONT).with_font_size(16.0),
+                        TextColor(tailwind::INDIGO_950.into()),
+                    ));
+                }
+            });
+    }
+}
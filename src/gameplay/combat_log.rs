@@ -0,0 +1,274 @@
+use std::{collections::VecDeque, fmt};
+
+use bevy::prelude::*;
+use serde_json::{Value, json};
+
+use crate::{
+    AppSystems, PausableSystems,
+    data::{
+        Tower,
+        projectiles::{DamageType, Puddle},
+        status_effects::{Reaction, StatusEffectTrait, StatusEnum},
+    },
+    demo::enemy_health::DoDamageToEnemy,
+    gameplay::status_effects::common::{ReactionTriggered, RemoveStatus, TryApplyStatus},
+    screens::Screen,
+};
+
+const COMBAT_LOG_CAPACITY: usize = 1000;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CombatLog>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_combat_log);
+    app.add_systems(
+        Update,
+        (record_status_applications, record_reactions, record_damage)
+            .in_set(PausableSystems)
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Where a log entry came from, resolved from the raw source entity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CombatSource {
+    Tower(Tower, Entity),
+    Puddle(Entity),
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CombatLogKind {
+    StatusApplied(StatusEnum, usize),
+    StatusRemoved(StatusEnum, usize),
+    Reaction(Reaction),
+    Damage(DamageType, isize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CombatLogEntry {
+    pub time: f32,
+    pub enemy: Entity,
+    pub kind: CombatLogKind,
+    pub source: CombatSource,
+}
+
+#[derive(Resource)]
+pub struct CombatLog {
+    entries: VecDeque<CombatLogEntry>,
+    capacity: usize,
+}
+
+impl Default for CombatLog {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(COMBAT_LOG_CAPACITY),
+            capacity: COMBAT_LOG_CAPACITY,
+        }
+    }
+}
+
+impl CombatLog {
+    pub fn push(&mut self, entry: CombatLogEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &CombatLogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn for_enemy(&self, enemy: Entity) -> impl DoubleEndedIterator<Item = &CombatLogEntry> {
+        self.entries.iter().filter(move |w| w.enemy == enemy)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries: Vec<_> = self.entries.iter().map(CombatLogEntry::to_json).collect();
+        serde_json::to_string_pretty(&entries).unwrap_or_default()
+    }
+}
+
+impl CombatSource {
+    fn to_json(&self) -> Value {
+        match self {
+            CombatSource::Tower(tower, entity) => json!({
+                "tower": tower.name(),
+                "entity": entity.to_string(),
+            }),
+            CombatSource::Puddle(entity) => json!({ "puddle": entity.to_string() }),
+            CombatSource::Unknown => Value::Null,
+        }
+    }
+}
+
+impl CombatLogEntry {
+    fn to_json(&self) -> Value {
+        let kind = match self.kind {
+            CombatLogKind::StatusApplied(status, strength) => json!({
+                "type": "status_applied",
+                "status": status.name(),
+                "strength": strength,
+            }),
+            CombatLogKind::StatusRemoved(status, strength) => json!({
+                "type": "status_removed",
+                "status": status.name(),
+                "strength": strength,
+            }),
+            CombatLogKind::Reaction(reaction) => json!({
+                "type": "reaction",
+                "reaction": reaction.name(),
+            }),
+            CombatLogKind::Damage(damage_type, damage) => json!({
+                "type": "damage",
+                "damage_type": format!("{damage_type:?}"),
+                "damage": damage,
+            }),
+        };
+        json!({
+            "time": self.time,
+            "enemy": self.enemy.to_string(),
+            "kind": kind,
+            "source": self.source.to_json(),
+        })
+    }
+}
+
+impl fmt::Display for CombatSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CombatSource::Tower(tower, entity) => write!(f, "{} ({entity})", tower.name()),
+            CombatSource::Puddle(entity) => write!(f, "Puddle ({entity})"),
+            CombatSource::Unknown => write!(f, "-"),
+        }
+    }
+}
+
+impl fmt::Display for CombatLogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>8.2}] {} ", self.time, self.enemy)?;
+        match self.kind {
+            CombatLogKind::StatusApplied(status, strength) => write!(f, "+{status:?} x{strength}")?,
+            CombatLogKind::StatusRemoved(status, strength) => write!(f, "-{status:?} x{strength}")?,
+            CombatLogKind::Reaction(reaction) => write!(f, "reaction {}", reaction.name())?,
+            CombatLogKind::Damage(damage_type, damage) => {
+                write!(f, "{damage} {damage_type:?} damage")?
+            }
+        }
+        write!(f, " from {}", self.source)
+    }
+}
+
+// Attacks usually come from the trigger zone, so walk up until we find the tower
+fn resolve_source(
+    source: Option<Entity>,
+    towers: &Query<&Tower>,
+    puddles: &Query<(), With<Puddle>>,
+    parents: &Query<&ChildOf>,
+) -> CombatSource {
+    let Some(source) = source else {
+        return CombatSource::Unknown;
+    };
+    if puddles.get(source).is_ok() {
+        return CombatSource::Puddle(source);
+    }
+    let mut current = source;
+    loop {
+        if let Ok(tower) = towers.get(current) {
+            return CombatSource::Tower(*tower, current);
+        }
+        let Ok(parent) = parents.get(current) else {
+            return CombatSource::Unknown;
+        };
+        current = parent.parent();
+    }
+}
+
+fn clear_combat_log(mut log: ResMut<CombatLog>) {
+    log.clear();
+}
+
+fn record_status_applications(
+    mut events: EventReader<TryApplyStatus>,
+    mut log: ResMut<CombatLog>,
+    time: Res<Time>,
+    towers: Query<&Tower>,
+    puddles: Query<(), With<Puddle>>,
+    parents: Query<&ChildOf>,
+) {
+    for event in events.read() {
+        log.push(CombatLogEntry {
+            time: time.elapsed_secs(),
+            enemy: event.enemy,
+            kind: CombatLogKind::StatusApplied(event.status, event.strength),
+            source: resolve_source(event.source, &towers, &puddles, &parents),
+        });
+    }
+}
+
+pub fn record_status_removal<T: StatusEffectTrait>(
+    mut events: EventReader<RemoveStatus<T>>,
+    mut log: ResMut<CombatLog>,
+    time: Res<Time>,
+    towers: Query<&Tower>,
+    puddles: Query<(), With<Puddle>>,
+    parents: Query<&ChildOf>,
+) {
+    for event in events.read() {
+        log.push(CombatLogEntry {
+            time: time.elapsed_secs(),
+            enemy: event.enemy,
+            kind: CombatLogKind::StatusRemoved(T::corresponding_enum(), event.strength),
+            source: resolve_source(event.source, &towers, &puddles, &parents),
+        });
+    }
+}
+
+fn record_reactions(
+    mut events: EventReader<ReactionTriggered>,
+    mut log: ResMut<CombatLog>,
+    time: Res<Time>,
+    towers: Query<&Tower>,
+    puddles: Query<(), With<Puddle>>,
+    parents: Query<&ChildOf>,
+) {
+    for event in events.read() {
+        log.push(CombatLogEntry {
+            time: time.elapsed_secs(),
+            enemy: event.enemy,
+            kind: CombatLogKind::Reaction(event.reaction),
+            source: resolve_source(event.source, &towers, &puddles, &parents),
+        });
+    }
+}
+
+fn record_damage(
+    mut events: EventReader<DoDamageToEnemy>,
+    mut log: ResMut<CombatLog>,
+    time: Res<Time>,
+    towers: Query<&Tower>,
+    puddles: Query<(), With<Puddle>>,
+    parents: Query<&ChildOf>,
+) {
+    for event in events.read() {
+        log.push(CombatLogEntry {
+            time: time.elapsed_secs(),
+            enemy: event.enemy,
+            kind: CombatLogKind::Damage(event.damage_type, event.damage),
+            source: resolve_source(event.source, &towers, &puddles, &parents),
+        });
+    }
+}
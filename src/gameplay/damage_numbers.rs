@@ -39,7 +39,12 @@ fn show_damage_numbers(
         commands.spawn((
             StateScoped(Screen::Gameplay),
             Text2d::new(event.damage.to_string()),
-            TextColor(event.damage_type.color()),
+            TextColor(
+                event
+                    .status
+                    .map(|w| w.color())
+                    .unwrap_or(event.damage_type.color()),
+            ),
             TextFont::from_font_size(18.0).with_font(TITLE_FONT),
             DamageNumber,
             DamageNumberLifetime {
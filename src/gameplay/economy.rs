@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PausableSystems,
+    data::{
+        PlayerState,
+        levels::{EconomyConfig, LevelData},
+    },
+    demo::enemy_health::EnemyHealth,
+    gameplay::{messages::DisplayFlashMessage, wave_manager::WaveManager},
+    level::resource::LevelSelect,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Economy>();
+    app.add_systems(OnEnter(Screen::Gameplay), load_economy);
+    app.add_systems(
+        Update,
+        pay_wave_income
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(on_wave_called);
+}
+
+// Fired by the Next Wave button, early when enemies are still on the field
+#[derive(Event, Debug, Clone, Copy)]
+pub struct WaveCalled {
+    pub early: bool,
+}
+
+// Fired after a wave's income has been paid out
+#[derive(Event, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WaveIncome {
+    pub wave_bonus: i32,
+    pub interest: i32,
+    pub early_call: i32,
+}
+
+impl WaveIncome {
+    pub fn total(&self) -> i32 {
+        self.wave_bonus + self.interest + self.early_call
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Economy {
+    pub config: EconomyConfig,
+    pub waves_cleared: i32,
+    // Waves called but not yet paid for
+    unpaid_waves: i32,
+    // Early call bonuses already paid, reported with the next wave income
+    early_call_paid: i32,
+}
+
+impl Economy {
+    pub fn new(config: EconomyConfig) -> Self {
+        Self {
+            config,
+            ..default()
+        }
+    }
+
+    pub fn interest(&self, money: i32) -> i32 {
+        ((money.max(0) as f32 * self.config.interest_rate) as i32).min(self.config.interest_cap)
+    }
+
+    pub fn wave_bonus(&self) -> i32 {
+        self.config.wave_bonus + self.config.wave_bonus_growth * self.waves_cleared
+    }
+}
+
+fn load_economy(
+    mut economy: ResMut<Economy>,
+    level_data: Res<LevelData>,
+    level_select: Res<LevelSelect>,
+) {
+    let config = level_data
+        .economies
+        .get(level_select.0)
+        .copied()
+        .unwrap_or_default();
+    *economy = Economy::new(config);
+}
+
+fn on_wave_called(
+    trigger: Trigger<WaveCalled>,
+    mut economy: ResMut<Economy>,
+    mut player_state: ResMut<PlayerState>,
+    mut commands: Commands,
+) {
+    economy.unpaid_waves += 1;
+
+    let bonus = economy.config.early_call_bonus;
+    if trigger.early && bonus > 0 {
+        player_state.money += bonus;
+        economy.early_call_paid += bonus;
+        commands.trigger(DisplayFlashMessage::new(format!("Early call +{bonus}")));
+    }
+}
+
+// A wave only counts as cleared once nothing is left on the field
+fn pay_wave_income(
+    wave_manager: Res<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut economy: ResMut<Economy>,
+    mut player_state: ResMut<PlayerState>,
+    mut commands: Commands,
+) {
+    if economy.unpaid_waves == 0 || wave_manager.current_wave.is_some() || !enemies.is_empty() {
+        return;
+    }
+
+    let mut wave_bonus = 0;
+    for _ in 0..economy.unpaid_waves {
+        wave_bonus += economy.wave_bonus();
+        economy.waves_cleared += 1;
+    }
+    // Interest is paid on what was banked before this payout
+    let income = WaveIncome {
+        wave_bonus,
+        interest: economy.interest(player_state.money),
+        early_call: economy.early_call_paid,
+    };
+
+    player_state.money += income.wave_bonus + income.interest;
+    economy.unpaid_waves = 0;
+    economy.early_call_paid = 0;
+    commands.trigger(income);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn config() -> EconomyConfig {
+        EconomyConfig {
+            wave_bonus: 50,
+            wave_bonus_growth: 10,
+            interest_rate: 0.1,
+            interest_cap: 30,
+            early_call_bonus: 25,
+        }
+    }
+
+    #[test]
+    fn interest_is_capped() {
+        let economy = Economy::new(config());
+        assert_eq!(economy.interest(100), 10);
+        assert_eq!(economy.interest(1000), 30);
+    }
+
+    #[test]
+    fn debt_earns_no_interest() {
+        let economy = Economy::new(config());
+        assert_eq!(economy.interest(-100), 0);
+    }
+
+    #[test]
+    fn wave_bonus_grows_with_cleared_waves() {
+        let mut economy = Economy::new(config());
+        assert_eq!(economy.wave_bonus(), 50);
+        economy.waves_cleared = 3;
+        assert_eq!(economy.wave_bonus(), 80);
+    }
+
+    fn world(money: i32) -> World {
+        let mut world = World::new();
+        world.insert_resource(Economy::new(config()));
+        world.insert_resource(PlayerState { money, ..default() });
+        world.insert_resource(WaveManager::default());
+        world.add_observer(on_wave_called);
+        world
+    }
+
+    #[test]
+    fn cleared_waves_pay_bonus_and_interest() {
+        let mut world = world(100);
+        world.trigger(WaveCalled { early: false });
+        world.run_system_once(pay_wave_income).unwrap();
+
+        // Interest on the 100 banked before the 50 bonus
+        assert_eq!(world.resource::<PlayerState>().money, 160);
+        assert_eq!(world.resource::<Economy>().waves_cleared, 1);
+    }
+
+    #[test]
+    fn early_calls_pay_up_front() {
+        let mut world = world(100);
+        world.trigger(WaveCalled { early: true });
+        assert_eq!(world.resource::<PlayerState>().money, 125);
+
+        world.trigger(WaveCalled { early: false });
+        world.run_system_once(pay_wave_income).unwrap();
+        // Both waves pay their bonus, the second one grown by the first
+        assert_eq!(world.resource::<PlayerState>().money, 125 + 50 + 60 + 12);
+    }
+
+    #[test]
+    fn nothing_is_paid_while_enemies_remain() {
+        let mut world = world(100);
+        world.spawn(EnemyHealth::new(10));
+        world.trigger(WaveCalled { early: false });
+        world.run_system_once(pay_wave_income).unwrap();
+
+        assert_eq!(world.resource::<PlayerState>().money, 100);
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::{color::palettes::tailwind, input::mouse::AccumulatedMouseMotion, prelude::*};
+
+use crate::{
+    PausableSystems,
+    data::PointerInteractionState,
+    gameplay::tower_placement::{
+        Placer, SpawnedPreview, TowerPlacementEvent, TowerPreview, TowerSeller, mounts_in_cell,
+    },
+    input::{Action, Actions},
+    level::components::{Adjacent, LEVEL_SCALING},
+    menus::Menu,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GridCursor>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (reset_grid_cursor, spawn_cursor_marker),
+    );
+    app.add_systems(
+        Update,
+        (
+            release_on_mouse_move,
+            move_grid_cursor,
+            preview_at_cursor.run_if(
+                resource_changed::<GridCursor>.or(state_changed::<PointerInteractionState>),
+            ),
+            place_at_cursor,
+            sell_at_cursor,
+            update_cursor_marker.run_if(resource_changed::<GridCursor>),
+        )
+            .chain()
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay).and(in_state(Menu::None))),
+    );
+}
+
+// The cell picked with the keyboard or a gamepad, None while the mouse is in use
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GridCursor(pub Option<IVec2>);
+
+#[derive(Component)]
+struct GridCursorMarker;
+
+fn reset_grid_cursor(mut cursor: ResMut<GridCursor>) {
+    *cursor = GridCursor(None);
+}
+
+fn spawn_cursor_marker(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Grid Cursor"),
+        GridCursorMarker,
+        StateScoped(Screen::Gameplay),
+        Sprite::from_color(
+            tailwind::SKY_400.with_alpha(0.3),
+            Vec2::splat(LEVEL_SCALING),
+        ),
+        Transform::from_xyz(0., 0., 1.),
+        Visibility::Hidden,
+    ));
+}
+
+fn release_on_mouse_move(motion: Res<AccumulatedMouseMotion>, mut cursor: ResMut<GridCursor>) {
+    if motion.delta != Vec2::ZERO {
+        cursor.set_if_neq(GridCursor(None));
+    }
+}
+
+fn move_grid_cursor(
+    actions: Actions,
+    preview: Res<TowerPreview>,
+    pieces: Query<(Entity, &Adjacent)>,
+    mut cursor: ResMut<GridCursor>,
+) {
+    let step = [
+        (Action::Up, IVec2::Y),
+        (Action::Down, IVec2::NEG_Y),
+        (Action::Left, IVec2::NEG_X),
+        (Action::Right, IVec2::X),
+    ]
+    .into_iter()
+    .filter(|(action, _)| actions.just_pressed(*action))
+    .map(|(_, step)| step)
+    .sum::<IVec2>();
+    if step == IVec2::ZERO {
+        return;
+    }
+
+    let cells: Vec<_> = pieces.iter().map(|(_, w)| w.cell()).collect();
+    let (Some(min), Some(max)) = (
+        cells.iter().copied().reduce(IVec2::min),
+        cells.iter().copied().reduce(IVec2::max),
+    ) else {
+        return;
+    };
+
+    // The first press only shows the cursor, where the mouse left off if possible,
+    // otherwise on a cell that actually has a piece
+    let cell = match cursor.0 {
+        Some(cell) => (cell + step).clamp(min, max),
+        None => preview
+            .position_entity
+            .and_then(|w| pieces.get(w).ok())
+            .map_or(cells[0], |(_, w)| w.cell()),
+    };
+    cursor.set_if_neq(GridCursor(Some(cell)));
+}
+
+// Previews the tower on the first slot of the cursor's cell, rotating picks the others
+fn preview_at_cursor(
+    cursor: Res<GridCursor>,
+    pointer_state: Res<State<PointerInteractionState>>,
+    mut preview: ResMut<TowerPreview>,
+    pieces: Query<(Entity, &Adjacent)>,
+    spawned_previews: Query<Entity, With<SpawnedPreview>>,
+    mut tower_placement_writer: EventWriter<TowerPlacementEvent>,
+    mut commands: Commands,
+) {
+    let (Some(cell), PointerInteractionState::Placing(tower)) = (cursor.0, *pointer_state.get())
+    else {
+        return;
+    };
+
+    let mounts = mounts_in_cell(tower, cell, &pieces);
+    let already_shown = preview.tower == Some(tower)
+        && mounts
+            .iter()
+            .any(|(w, _)| preview.position_entity == Some(*w));
+    if already_shown {
+        return;
+    }
+
+    match mounts.first() {
+        Some((piece, placement)) => {
+            tower_placement_writer.write(TowerPlacementEvent::Requested(tower, *piece, *placement));
+        }
+        None => {
+            for entity in spawned_previews.iter() {
+                commands.entity(entity).despawn();
+            }
+            *preview = TowerPreview::default();
+        }
+    }
+}
+
+fn place_at_cursor(
+    actions: Actions,
+    cursor: Res<GridCursor>,
+    pointer_state: Res<State<PointerInteractionState>>,
+    mut placer: Placer,
+) {
+    if cursor.0.is_none()
+        || !matches!(pointer_state.get(), PointerInteractionState::Placing(_))
+        || !actions.just_pressed(Action::Confirm)
+    {
+        return;
+    }
+    placer.place_preview(actions.pressed(Action::Modifier));
+}
+
+fn sell_at_cursor(actions: Actions, cursor: Res<GridCursor>, mut seller: TowerSeller) {
+    let Some(cell) = cursor.0 else {
+        return;
+    };
+    if actions.just_pressed(Action::Sell) {
+        seller.sell_near(cell.as_vec2() * LEVEL_SCALING, LEVEL_SCALING * 0.75);
+    }
+}
+
+fn update_cursor_marker(
+    cursor: Res<GridCursor>,
+    mut markers: Query<(&mut Transform, &mut Visibility), With<GridCursorMarker>>,
+) {
+    for (mut transform, mut visibility) in markers.iter_mut() {
+        match cursor.0 {
+            Some(cell) => {
+                transform.translation = (cell.as_vec2() * LEVEL_SCALING).extend(1.);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
@@ -1,8 +1,13 @@
+use crate::data::levels::LevelData;
+use crate::data::tutorials::TutorialUi;
 use crate::gameplay::messages::DisplayFlashMessage;
+use crate::gameplay::tutorial::TutorialAnchor;
+use crate::input::{Action, Actions, InputMap, action_just_pressed, keys_label};
+use crate::level::resource::LevelSelect;
+use crate::menus::Menu;
 use crate::theme::palette::LABEL_TEXT;
 use crate::{data::*, prelude::*, theme::prelude::*};
 use bevy::color::palettes::tailwind;
-use bevy::input::common_conditions::input_just_pressed;
 use bevy::{ecs::spawn::*, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
@@ -16,27 +21,63 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        unset_cursor_state.run_if(input_just_pressed(KeyCode::Escape)),
+        unset_cursor_state.run_if(action_just_pressed(Action::Back)),
     );
 
     app.add_systems(Update, watch_pointer_state);
+    app.add_systems(
+        Update,
+        (
+            (select_with_hotkey, cycle_hotbar).run_if(in_state(Menu::None)),
+            grey_out_unaffordable
+                .run_if(resource_changed::<PlayerState>.or(any_match_filter::<Added<HotbarIcon>>)),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
 }
 
 #[derive(Component, Debug, Reflect)]
 pub struct HotbarItem;
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+struct HotbarSlot(usize);
+
+#[derive(Component, Debug, Clone, Copy)]
+struct HotbarIcon(Tower);
+
 #[derive(Component)]
 struct CancelInput;
 
-fn on_enter_game(mut commands: Commands, assets: Res<UiAssets>) {
-    let mut towers = Tower::all();
+fn on_enter_game(
+    mut commands: Commands,
+    assets: Res<UiAssets>,
+    level_data: Res<LevelData>,
+    level_select: Res<LevelSelect>,
+    input_map: Res<InputMap>,
+) {
+    let mut towers = level_data.setup(level_select.0).towers;
     towers.sort_by(|a, b| a.price().cmp(&b.price()));
+    let aim_fan = Some(input_map.keys(Action::AimFan))
+        .filter(|w| !w.is_empty())
+        .map(keys_label);
 
     let hotbar_items: Vec<_> = towers
         .iter()
-        .map(|t| {
+        .enumerate()
+        .map(|(slot, t)| {
             let asset = assets.hotbar_icons.get(t.ui_asset_key()).unwrap().clone();
-            (*t, asset)
+            let hotkey = Action::HOTBAR
+                .get(slot)
+                .map(|w| input_map.keys(*w))
+                .filter(|w| !w.is_empty())
+                .map(keys_label);
+            let description = match (t, &aim_fan) {
+                (Tower::Fan, Some(keys)) => {
+                    format!("{} Press {keys} while placing to aim it.", t.description())
+                }
+                _ => t.description().to_string(),
+            };
+            (*t, asset, hotkey, description)
         })
         .collect();
 
@@ -44,11 +85,11 @@ fn on_enter_game(mut commands: Commands, assets: Res<UiAssets>) {
         .spawn((
             StateScoped(Screen::Gameplay),
             spawn_hotbar(),
-            Children::spawn(SpawnIter(
-                hotbar_items
-                    .into_iter()
-                    .map(|(tower, icon)| spawn_hotbar_item(tower, icon)),
-            )),
+            Children::spawn(SpawnIter(hotbar_items.into_iter().enumerate().map(
+                |(slot, (tower, icon, hotkey, description))| {
+                    spawn_hotbar_item(slot, tower, icon, hotkey, description)
+                },
+            ))),
         ))
         .observe(hotbar_click_observer);
 
@@ -128,7 +169,27 @@ fn spawn_hotbar() -> impl Bundle {
     )
 }
 
-fn spawn_hotbar_item(tower: Tower, icon: Handle<Image>) -> impl Bundle {
+fn spawn_hotbar_item(
+    slot: usize,
+    tower: Tower,
+    icon: Handle<Image>,
+    hotkey: Option<String>,
+    description: String,
+) -> impl Bundle {
+    let damage = match tower.attack_def().damage_types().as_slice() {
+        [] => "None".to_string(),
+        types => types
+            .iter()
+            .map(|w| w.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    let cooldown = if tower.cooldown() > 0. {
+        format!("{}s", tower.cooldown())
+    } else {
+        "Always active".to_string()
+    };
+
     (
         Name::new(tower.name()),
         Button,
@@ -141,11 +202,14 @@ fn spawn_hotbar_item(tower: Tower, icon: Handle<Image>) -> impl Bundle {
         BorderColor(Color::WHITE),
         BorderRadius::all(Val::Px(8.0)),
         HotbarItem,
+        HotbarSlot(slot),
+        TutorialAnchor(TutorialUi::Hotbar(tower)),
         tower,
         TooltipParent,
         children![
             (
                 Tooltip,
+                Visibility::Hidden,
                 BackgroundColor(Color::BLACK.with_alpha(0.85)),
                 BorderRadius::all(Val::Px(4.0)),
                 BorderColor(LABEL_TEXT),
@@ -161,8 +225,10 @@ fn spawn_hotbar_item(tower: Tower, icon: Handle<Image>) -> impl Bundle {
                 },
                 Children::spawn((
                     Spawn(widget::label(tower.name())),
-                    Spawn(widget::body_text(tower.description())),
+                    Spawn(widget::body_text(description)),
                     Spawn(widget::body_text(format!("Cost: {}", tower.price()))),
+                    Spawn(widget::body_text(format!("Cooldown: {cooldown}"))),
+                    Spawn(widget::body_text(format!("Damage: {damage}"))),
                 )),
             ),
             (
@@ -173,7 +239,18 @@ fn spawn_hotbar_item(tower: Tower, icon: Handle<Image>) -> impl Bundle {
                     ..default()
                 },
                 Pickable::IGNORE,
-                ImageNode::new(icon)
+                HotbarIcon(tower),
+                ImageNode::new(icon).with_color(tower.tint())
+            ),
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(2.0),
+                    left: Val::Px(6.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                Children::spawn(SpawnIter(hotkey.map(widget::label).into_iter())),
             )
         ],
     )
@@ -207,10 +284,98 @@ fn hotbar_click_observer(
     let Ok(tower) = hotbar_items.get(trigger.target) else {
         return;
     };
+    select_tower(
+        *tower,
+        &player_state,
+        &mut pointer_input_state,
+        &mut commands,
+    );
+}
+
+fn select_with_hotkey(
+    actions: Actions,
+    hotbar_items: Query<(&HotbarSlot, &Tower)>,
+    player_state: Res<PlayerState>,
+    mut pointer_input_state: ResMut<NextState<PointerInteractionState>>,
+    mut commands: Commands,
+) {
+    let Some(slot) = Action::HOTBAR.iter().position(|w| actions.just_pressed(*w)) else {
+        return;
+    };
+    let Some((_, tower)) = hotbar_items.iter().find(|(w, _)| w.0 == slot) else {
+        return;
+    };
+    select_tower(
+        *tower,
+        &player_state,
+        &mut pointer_input_state,
+        &mut commands,
+    );
+}
+
+// Steps to the next affordable tower, wrapping around the hotbar
+fn cycle_hotbar(
+    actions: Actions,
+    hotbar_items: Query<(&HotbarSlot, &Tower)>,
+    player_state: Res<PlayerState>,
+    pointer_state: Res<State<PointerInteractionState>>,
+    mut pointer_input_state: ResMut<NextState<PointerInteractionState>>,
+    mut commands: Commands,
+) {
+    let step = if actions.just_pressed(Action::HotbarNext) {
+        1
+    } else if actions.just_pressed(Action::HotbarPrevious) {
+        -1
+    } else {
+        return;
+    };
+
+    let mut slots: Vec<_> = hotbar_items.iter().map(|(w, t)| (w.0, *t)).collect();
+    slots.sort_by_key(|(slot, _)| *slot);
+    let count = slots.len() as isize;
+    let current = match pointer_state.get() {
+        PointerInteractionState::Placing(tower) => slots
+            .iter()
+            .position(|(_, w)| w == tower)
+            .map(|w| w as isize),
+        PointerInteractionState::Selecting => None,
+    };
+    // With nothing selected, next starts from the left and previous from the right
+    let start = current.unwrap_or(if step > 0 { -1 } else { count });
+
+    let next = (1..=count)
+        .map(|w| (start + w * step).rem_euclid(count) as usize)
+        .find(|w| player_state.can_afford(slots[*w].1.price()));
+    match next {
+        Some(index) => {
+            pointer_input_state.set(PointerInteractionState::Placing(slots[index].1));
+        }
+        None => commands.trigger(DisplayFlashMessage::new("Insufficient funds")),
+    }
+}
 
+fn select_tower(
+    tower: Tower,
+    player_state: &PlayerState,
+    pointer_input_state: &mut NextState<PointerInteractionState>,
+    commands: &mut Commands,
+) {
     if !player_state.can_afford(tower.price()) {
         commands.trigger(DisplayFlashMessage::new("Insufficient funds"));
         return;
     }
-    pointer_input_state.set(PointerInteractionState::Placing(*tower));
+    pointer_input_state.set(PointerInteractionState::Placing(tower));
+}
+
+fn grey_out_unaffordable(
+    player_state: Res<PlayerState>,
+    mut icons: Query<(&HotbarIcon, &mut ImageNode)>,
+) {
+    for (icon, mut image) in icons.iter_mut() {
+        image.color = if player_state.can_afford(icon.0.price()) {
+            icon.0.tint()
+        } else {
+            icon.0.tint().mix(&Color::BLACK, 0.6).with_alpha(0.5)
+        };
+    }
 }
@@ -1,13 +1,16 @@
 use crate::data::PlayerState;
+use crate::data::tutorials::TutorialUi;
 use crate::demo::enemy_health::BountyEarned;
+use crate::gameplay::economy::WaveIncome;
 use crate::gameplay::shared_systems::Lifetime;
+use crate::gameplay::tutorial::TutorialAnchor;
 use crate::gameplay::wave_manager::WaveManager;
 use crate::level::resource::CurrentLoadedLevel;
 use crate::prelude::*;
 use crate::theme::handles::LABEL_FONT;
 use crate::theme::widget;
 use bevy::color::palettes::tailwind;
-use bevy::prelude::*;
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
@@ -21,6 +24,7 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.add_systems(Update, animate_bounty_text);
     app.add_observer(bounty_earned);
+    app.add_observer(wave_income);
 }
 
 #[derive(Component)]
@@ -29,6 +33,7 @@ enum HudElement {
     Health,
     Money,
     BountyEarned,
+    WaveIncome,
 }
 
 fn on_enter_game(mut commands: Commands) {
@@ -56,8 +61,16 @@ fn on_enter_game(mut commands: Commands) {
                     ..default()
                 },
                 children![
-                    (widget::ui_font("Lives: _"), HudElement::Health),
-                    (widget::ui_font("Money: _"), HudElement::Money),
+                    (
+                        widget::ui_font("Lives: _"),
+                        HudElement::Health,
+                        TutorialAnchor(TutorialUi::Lives)
+                    ),
+                    (
+                        widget::ui_font("Money: _"),
+                        HudElement::Money,
+                        TutorialAnchor(TutorialUi::Money)
+                    ),
                 ]
             ),
         ],
@@ -119,6 +132,43 @@ fn bounty_earned(trigger: Trigger<BountyEarned>, mut commands: Commands) {
     ));
 }
 
+fn wave_income(trigger: Trigger<WaveIncome>, mut commands: Commands) {
+    let income = *trigger.event();
+
+    let mut lines = vec![format!("Wave cleared +{}", income.wave_bonus)];
+    if income.interest > 0 {
+        lines.push(format!("Interest +{}", income.interest));
+    }
+    if income.early_call > 0 {
+        lines.push(format!("Early call +{}", income.early_call));
+    }
+    lines.push(format!("Total +{}", income.total()));
+
+    commands.spawn((
+        Lifetime::new(4.0),
+        StateScoped(Screen::Gameplay),
+        HudElement::WaveIncome,
+        Name::new("Wave Income"),
+        Node {
+            top: Val::Px(64.0),
+            right: Val::Px(240.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(tailwind::INDIGO_300.with_alpha(0.8).into()),
+        Pickable::IGNORE,
+        Children::spawn(SpawnIter(lines.into_iter().map(|line| {
+            (
+                Text(line),
+                TextFont::from_font(LABEL_FONT).with_font_size(18.0),
+                TextColor(tailwind::INDIGO_950.into()),
+            )
+        }))),
+    ));
+}
+
 fn animate_bounty_text(mut query: Query<(&HudElement, &mut Node)>, time: Res<Time>) {
     for (entity, mut node) in query.iter_mut() {
         match entity {
@@ -12,7 +12,9 @@ use crate::audio::sound_effect;
 use crate::data::levels::LevelData;
 use crate::gameplay::level;
 use crate::gameplay::wave_manager::WaveManager;
-use crate::level::resource::{CurrentLoadedLevel, GotoNextLevel, LevelSelect, UnlockedLevels};
+use crate::level::resource::{
+    CurrentLoadedLevel, GotoNextLevel, LevelCompleted, LevelSelect, UnlockedLevels,
+};
 use crate::{
     assets::LevelAssets,
     audio::music,
@@ -84,6 +86,10 @@ pub fn unlock_next_level(
     current_loaded_level: Res<CurrentLoadedLevel>,
     mut unlocked_levels: ResMut<UnlockedLevels>,
     enemies: Query<(), With<EnemyHealth>>,
+    player_state: Res<PlayerState>,
+    level_parent: Query<Entity, With<LevelParent>>,
+    mut completed: Local<Option<Entity>>,
+    mut commands: Commands,
 ) {
     if current_loaded_level.0 != level_select.0 {
         return;
@@ -96,18 +102,28 @@ pub fn unlock_next_level(
         if !unlocked_levels.0.contains(&next_level) {
             unlocked_levels.0.push(next_level);
         }
+
+        // Each attempt spawns a fresh level parent, so this fires once per attempt
+        let parent = level_parent.single().ok();
+        if parent.is_some() && *completed != parent {
+            *completed = parent;
+            commands.trigger(LevelCompleted {
+                level: level_select.0,
+                lives: player_state.health,
+                stars: player_state.stars(),
+            });
+        }
     }
 }
 
+// The victory screen picks the next level, this only gets the player there
 pub fn goto_next_level(
     mut _event: EventReader<GotoNextLevel>,
-    mut level_select: ResMut<LevelSelect>,
     mut next_screen: ResMut<NextState<Screen>>,
     enemies: Query<(), With<EnemyHealth>>,
 ) {
     if enemies.iter().len() == 0 {
-        level_select.0 += 1;
-        next_screen.set(Screen::LevelTransition);
+        next_screen.set(Screen::Victory);
     }
 }
 
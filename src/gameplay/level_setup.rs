@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::levels::{LevelData, PreplacedTower},
+    gameplay::{
+        action_history::TowerSnapshot, run_save::ResumeRun, tower_placement::spawn_tower,
+        towers::targeting::TargetPriority,
+    },
+    level::{components::Adjacent, resource::LevelSelect},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        queue_preplaced_towers.run_if(not(resource_exists::<ResumeRun>)),
+    );
+    app.add_systems(
+        Update,
+        spawn_preplaced_towers
+            .run_if(resource_exists::<PendingPreplaced>)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Marks a tower the level came with, it was free so selling it refunds nothing
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub struct Preplaced {
+    pub locked: bool,
+}
+
+// Preplaced towers waiting for the level pieces to exist
+#[derive(Resource, Debug, Clone)]
+struct PendingPreplaced(Vec<PreplacedTower>);
+
+fn queue_preplaced_towers(
+    level_data: Res<LevelData>,
+    level_select: Res<LevelSelect>,
+    mut commands: Commands,
+) {
+    let preplaced = level_data.setup(level_select.0).preplaced;
+    if !preplaced.is_empty() {
+        commands.insert_resource(PendingPreplaced(preplaced));
+    }
+}
+
+fn spawn_preplaced_towers(
+    pending: Res<PendingPreplaced>,
+    pieces: Query<(Entity, &Adjacent)>,
+    mut commands: Commands,
+) {
+    if pieces.is_empty() {
+        return;
+    }
+
+    for preplaced in &pending.0 {
+        let placement = &preplaced.placement;
+        let Some((piece, _)) = placement.find_piece(pieces.iter()) else {
+            warn!("No piece for preplaced {:?}", placement);
+            continue;
+        };
+        let snapshot = TowerSnapshot {
+            tower: placement.tower,
+            piece,
+            orientation: placement.orientation,
+            fan_direction: placement.fan_direction,
+            priority: TargetPriority::default(),
+            preplaced: Some(Preplaced {
+                locked: preplaced.locked,
+            }),
+        };
+        spawn_tower(&mut commands, snapshot);
+    }
+    commands.remove_resource::<PendingPreplaced>();
+}
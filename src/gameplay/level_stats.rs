@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    data::{PlayerState, Tower, status_effects::Reaction},
+    demo::enemy_health::KillEnemy,
+    gameplay::{
+        combat_log::{CombatLog, CombatLogKind, CombatSource},
+        level_setup::Preplaced,
+        status_effects::common::ReactionTriggered,
+    },
+    level::resource::LevelCompleted,
+    screens::Screen,
+};
+
+const TOP_REACTIONS: usize = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelStats>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_level_stats);
+    app.add_systems(
+        Update,
+        (record_kills, record_reactions)
+            .in_set(PausableSystems)
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(summarize_level);
+}
+
+// Running tallies for the level being played
+#[derive(Resource, Debug, Default, Clone)]
+pub struct LevelStats {
+    pub kills: HashMap<Tower, u32>,
+    // Kills that can't be traced back to a tower, e.g. from puddles
+    pub other_kills: u32,
+    pub reactions: HashMap<Reaction, u32>,
+}
+
+// What the victory screen shows, kept after the level itself is despawned
+#[derive(Resource, Debug, Clone)]
+pub struct LevelSummary {
+    pub level: usize,
+    pub lives: i32,
+    pub max_lives: i32,
+    pub stars: u8,
+    pub money_spent: i32,
+    pub kills: Vec<(String, u32)>,
+    pub top_reactions: Vec<(Reaction, u32)>,
+}
+
+fn clear_level_stats(mut stats: ResMut<LevelStats>) {
+    *stats = LevelStats::default();
+}
+
+// The killing blow is whatever last damaged the enemy
+fn record_kills(
+    mut events: EventReader<KillEnemy>,
+    log: Res<CombatLog>,
+    mut stats: ResMut<LevelStats>,
+) {
+    for KillEnemy(enemy) in events.read() {
+        let killer = log
+            .for_enemy(*enemy)
+            .rev()
+            .find_map(|w| match (w.kind, w.source) {
+                (CombatLogKind::Damage(..), CombatSource::Tower(tower, _)) => Some(tower),
+                _ => None,
+            });
+        match killer {
+            Some(tower) => *stats.kills.entry(tower).or_default() += 1,
+            None => stats.other_kills += 1,
+        }
+    }
+}
+
+fn record_reactions(mut events: EventReader<ReactionTriggered>, mut stats: ResMut<LevelStats>) {
+    for event in events.read() {
+        *stats.reactions.entry(event.reaction).or_default() += 1;
+    }
+}
+
+fn summarize_level(
+    trigger: Trigger<LevelCompleted>,
+    stats: Res<LevelStats>,
+    player_state: Res<PlayerState>,
+    towers: Query<&Tower, Without<Preplaced>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    // Sold towers are refunded in full, so what is still standing is what was spent.
+    // Preplaced towers came for free
+    let money_spent = towers.iter().map(|w| w.price()).sum();
+
+    let mut kills: Vec<_> = stats
+        .kills
+        .iter()
+        .map(|(tower, count)| (tower.name().to_string(), *count))
+        .collect();
+    kills.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if stats.other_kills > 0 {
+        kills.push(("Other".to_string(), stats.other_kills));
+    }
+
+    let mut top_reactions: Vec<_> = stats.reactions.iter().map(|(w, n)| (*w, *n)).collect();
+    top_reactions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name().cmp(b.0.name())));
+    top_reactions.truncate(TOP_REACTIONS);
+
+    commands.insert_resource(LevelSummary {
+        level: trigger.level,
+        lives: trigger.lives,
+        max_lives: player_state.max_health,
+        stars: trigger.stars,
+        money_spent,
+        kills,
+        top_reactions,
+    });
+    next_screen.set(Screen::Victory);
+}
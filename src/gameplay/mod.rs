@@ -1,33 +1,60 @@
 use bevy::prelude::*;
 
+pub mod action_history;
 pub mod animation;
 mod background;
+pub mod blueprints;
+pub mod build_queue;
+pub mod combat_log;
 mod damage_numbers;
+pub mod economy;
+pub mod grid_cursor;
 pub mod hotbar;
 pub mod hud;
 pub mod level;
+pub mod level_setup;
+pub mod level_stats;
 pub mod messages;
+pub mod run_save;
 pub mod shared_systems;
 pub mod stats;
 pub mod status_effects;
+pub mod synergies;
+pub mod tower_panel;
 pub mod tower_placement;
 pub mod towers;
+pub mod tutorial;
 pub mod wave_manager;
 
 pub(super) fn plugin(app: &mut App) {
+    // Tuples of plugins are capped in length, so these are added in two groups
     app.add_plugins((
+        action_history::plugin,
         animation::plugin,
+        blueprints::plugin,
+        build_queue::plugin,
+        combat_log::plugin,
         damage_numbers::plugin,
+        economy::plugin,
+        grid_cursor::plugin,
         hotbar::plugin,
         hud::plugin,
         level::plugin,
+        level_setup::plugin,
+        level_stats::plugin,
+    ));
+    app.add_plugins((
         shared_systems::plugin,
         status_effects::plugin,
         stats::plugin,
+        synergies::plugin,
         towers::plugin,
+        tower_panel::plugin,
         tower_placement::plugin,
         wave_manager::plugin,
         messages::plugin,
+        run_save::plugin,
+        tutorial::plugin,
         background::plugin,
     ));
 }
@@ -0,0 +1,622 @@
+use std::{collections::HashMap, time::Duration};
+
+use avian2d::prelude::LinearVelocity;
+use bevy::{ecs::relationship::DescendantIter, prelude::*};
+use bevy_composable::app_impl::{ComplexSpawnable, ComponentTreeable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::game_assets::HEALTH_BAR_WIDTH,
+    data::{
+        PlayerState, Tower,
+        levels::LevelData,
+        projectiles::{AttackData, Droplet, LiquidPotency, LiquidType, Projectile, Puddle},
+        status_effects::{
+            Acidified, Burned, Chilled, Electrocuted, Frozen, Ignited, Magnetized, Oiled, Poisoned,
+            Slippery, StatusEffect, StatusEffectTrait, StatusEnum, Stunned, Wet,
+        },
+    },
+    demo::{
+        enemy_health::{EnemyHealth, EnemyHealthBar},
+        enemy_movement::PathProgress,
+    },
+    gameplay::{
+        action_history::TowerSnapshot,
+        blueprints::BlueprintTower,
+        economy::Economy,
+        level_setup::Preplaced,
+        shared_systems::Lifetime,
+        tower_placement::spawn_tower_with,
+        towers::{
+            common::Cooldown, directional::FireDirection, fan::Blown, targeting::TargetPriority,
+        },
+        wave_manager::{WaveManager, WaveProgress, level_waves},
+    },
+    level::{
+        components::{Adjacent, StartNode},
+        resource::{CellDirection, CurrentLoadedLevel},
+    },
+    persistence,
+    prefabs::{
+        attacks::{droplet, projectile, puddle},
+        enemies::EnemyKind,
+    },
+    screens::Screen,
+};
+
+const RUN_KEY: &str = "run";
+const RUN_VERSION: u32 = 2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        discard_saved_run.run_if(not(resource_exists::<ResumeRun>)),
+    );
+    app.add_systems(
+        Update,
+        (
+            resume_run.run_if(resource_exists::<ResumeRun>),
+            apply_restored_enemies,
+            apply_restored_projectiles,
+            forget_restored_sources,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(save_run);
+}
+
+// Asks for the level in progress to be written out, e.g. when quitting to title
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveRun;
+
+// Set before entering gameplay to pick up a saved run instead of starting fresh
+#[derive(Resource, Debug, Clone)]
+pub struct ResumeRun(pub RunSave);
+
+// What applied a status or fired a projectile, as an index into the saved towers or puddles
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SavedSource {
+    Tower(usize),
+    Puddle(usize),
+}
+
+// Marks what a saved source was respawned as, for the frame the run is restored in
+#[derive(Component, Clone, Copy, Debug)]
+struct RestoredFrom(SavedSource);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SavedStatus {
+    pub status: StatusEnum,
+    pub strength: usize,
+    pub elapsed: f32,
+    pub duration: f32,
+    pub source: Option<SavedSource>,
+    // Progress towards the next damage over time tick, and the damage carried over
+    pub dot_elapsed: f32,
+    pub dot_remainder: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SavedTower {
+    pub placement: BlueprintTower,
+    pub priority: TargetPriority,
+    // Elapsed and total seconds of the cooldown, if the tower was reloading
+    pub cooldown: Option<(f32, f32)>,
+    #[serde(default)]
+    pub preplaced: Option<Preplaced>,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SavedEnemy {
+    pub kind: EnemyKind,
+    pub translation: [f32; 3],
+    pub velocity: [f32; 2],
+    pub health: isize,
+    pub progress: f32,
+    pub statuses: Vec<SavedStatus>,
+    // Seconds since the enemy last felt a fan's wind
+    pub blown: Option<f32>,
+}
+
+// A droplet still falling, or a puddle it left behind
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SavedLiquid {
+    pub liquid: LiquidType,
+    pub potency: LiquidPotency,
+    pub translation: [f32; 3],
+    pub velocity: [f32; 2],
+    // Elapsed and total seconds, droplets live until they land
+    pub lifetime: Option<(f32, f32)>,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SavedProjectile {
+    pub tower: usize,
+    pub effects: Vec<AttackData>,
+    pub color: [f32; 4],
+    pub translation: [f32; 3],
+    pub velocity: [f32; 2],
+    pub lifetime: (f32, f32),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunSave {
+    pub version: u32,
+    pub level: usize,
+    pub player_state: PlayerState,
+    pub waves: WaveProgress,
+    pub economy: Economy,
+    pub towers: Vec<SavedTower>,
+    pub enemies: Vec<SavedEnemy>,
+    pub droplets: Vec<SavedLiquid>,
+    pub puddles: Vec<SavedLiquid>,
+    pub projectiles: Vec<SavedProjectile>,
+}
+
+pub fn load_saved_run() -> Option<RunSave> {
+    persistence::load::<RunSave>(RUN_KEY).filter(|w| w.version == RUN_VERSION)
+}
+
+pub fn discard_saved_run() {
+    persistence::remove(RUN_KEY);
+}
+
+// Entities don't survive a reload, so sources are saved as what they were
+struct SourceIndex<'a, 'w, 's> {
+    towers: HashMap<Entity, usize>,
+    puddles: HashMap<Entity, usize>,
+    parents: &'a Query<'w, 's, &'static ChildOf>,
+}
+
+impl SourceIndex<'_, '_, '_> {
+    // Attacks usually come from a tower's trigger zone, so walk up to the tower
+    fn find(&self, source: Option<Entity>) -> Option<SavedSource> {
+        let mut current = source?;
+        if let Some(index) = self.puddles.get(&current) {
+            return Some(SavedSource::Puddle(*index));
+        }
+        loop {
+            if let Some(index) = self.towers.get(&current) {
+                return Some(SavedSource::Tower(*index));
+            }
+            current = self.parents.get(current).ok()?.parent();
+        }
+    }
+}
+
+fn saved_status<T: StatusEffectTrait>(
+    entity: &EntityRef,
+    sources: &SourceIndex,
+) -> Option<SavedStatus> {
+    entity.get::<StatusEffect<T>>().map(|effect| SavedStatus {
+        status: T::corresponding_enum(),
+        strength: effect.strength,
+        elapsed: effect.duration.elapsed_secs(),
+        duration: effect.duration.duration().as_secs_f32(),
+        source: sources.find(effect.source),
+        dot_elapsed: effect.dot_tick.elapsed_secs(),
+        dot_remainder: effect.dot_remainder,
+    })
+}
+
+fn saved_statuses(entity: &EntityRef, sources: &SourceIndex) -> Vec<SavedStatus> {
+    [
+        saved_status::<Wet>(entity, sources),
+        saved_status::<Ignited>(entity, sources),
+        saved_status::<Burned>(entity, sources),
+        saved_status::<Chilled>(entity, sources),
+        saved_status::<Frozen>(entity, sources),
+        saved_status::<Electrocuted>(entity, sources),
+        saved_status::<Acidified>(entity, sources),
+        saved_status::<Oiled>(entity, sources),
+        saved_status::<Stunned>(entity, sources),
+        saved_status::<Poisoned>(entity, sources),
+        saved_status::<Magnetized>(entity, sources),
+        saved_status::<Slippery>(entity, sources),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn restored_status<T: StatusEffectTrait>(
+    saved: &SavedStatus,
+    source: Option<Entity>,
+) -> StatusEffect<T> {
+    let mut effect = StatusEffect::<T>::new(saved.strength, saved.duration).with_source(source);
+    effect
+        .duration
+        .set_elapsed(Duration::from_secs_f32(saved.elapsed));
+    effect
+        .dot_tick
+        .set_elapsed(Duration::from_secs_f32(saved.dot_elapsed));
+    effect.dot_remainder = saved.dot_remainder;
+    effect
+}
+
+fn insert_status(entity: &mut EntityCommands, saved: &SavedStatus, source: Option<Entity>) {
+    match saved.status {
+        StatusEnum::Wet => entity.insert(restored_status::<Wet>(saved, source)),
+        StatusEnum::Ignited => entity.insert(restored_status::<Ignited>(saved, source)),
+        StatusEnum::Burned => entity.insert(restored_status::<Burned>(saved, source)),
+        StatusEnum::Chilled => entity.insert(restored_status::<Chilled>(saved, source)),
+        StatusEnum::Frozen => entity.insert(restored_status::<Frozen>(saved, source)),
+        StatusEnum::Electrocuted => entity.insert(restored_status::<Electrocuted>(saved, source)),
+        StatusEnum::Acidified => entity.insert(restored_status::<Acidified>(saved, source)),
+        StatusEnum::Oiled => entity.insert(restored_status::<Oiled>(saved, source)),
+        StatusEnum::Stunned => entity.insert(restored_status::<Stunned>(saved, source)),
+        StatusEnum::Poisoned => entity.insert(restored_status::<Poisoned>(saved, source)),
+        StatusEnum::Magnetized => entity.insert(restored_status::<Magnetized>(saved, source)),
+        StatusEnum::Slippery => entity.insert(restored_status::<Slippery>(saved, source)),
+    };
+}
+
+fn saved_liquid(
+    liquid: LiquidType,
+    transform: &Transform,
+    velocity: Option<&LinearVelocity>,
+    potency: Option<&LiquidPotency>,
+    lifetime: Option<&Lifetime>,
+) -> SavedLiquid {
+    SavedLiquid {
+        liquid,
+        potency: potency.copied().unwrap_or_default(),
+        translation: transform.translation.to_array(),
+        velocity: velocity.map(|w| w.0.to_array()).unwrap_or_default(),
+        lifetime: lifetime.map(|w| (w.0.elapsed_secs(), w.0.duration().as_secs_f32())),
+    }
+}
+
+fn restored_lifetime((elapsed, duration): (f32, f32)) -> Lifetime {
+    let mut lifetime = Lifetime::new(duration);
+    lifetime.0.set_elapsed(Duration::from_secs_f32(elapsed));
+    lifetime
+}
+
+fn save_run(
+    _: Trigger<SaveRun>,
+    level: Res<CurrentLoadedLevel>,
+    level_data: Res<LevelData>,
+    player_state: Res<PlayerState>,
+    wave_manager: Res<WaveManager>,
+    economy: Res<Economy>,
+    towers: Query<(
+        Entity,
+        (&Tower, &ChildOf, &CellDirection, &TargetPriority),
+        Option<&FireDirection>,
+        Option<&Cooldown>,
+        Option<&Preplaced>,
+    )>,
+    pieces: Query<&Adjacent>,
+    parents: Query<&ChildOf>,
+    // Dying enemies already paid out their bounty
+    enemies: Query<EntityRef, (With<EnemyHealth>, Without<Lifetime>)>,
+    droplets: Query<(
+        &Droplet,
+        &Transform,
+        Option<&LinearVelocity>,
+        Option<&LiquidPotency>,
+    )>,
+    puddles: Query<(
+        Entity,
+        &Puddle,
+        &Transform,
+        Option<&LinearVelocity>,
+        Option<&LiquidPotency>,
+        Option<&Lifetime>,
+    )>,
+    projectiles: Query<(
+        &Projectile,
+        &Transform,
+        &Sprite,
+        Option<&LinearVelocity>,
+        &Lifetime,
+    )>,
+) {
+    let (tower_entities, towers): (Vec<_>, Vec<_>) = towers
+        .iter()
+        .filter_map(
+            |(
+                entity,
+                (tower, ChildOf(parent), orientation, priority),
+                fire_direction,
+                cooldown,
+                preplaced,
+            )| {
+                let piece = pieces.get(*parent).ok()?;
+                // Fans keep the way they blow, so their wind is rebuilt exactly
+                let saved = SavedTower {
+                    placement: BlueprintTower::new(*tower, piece, *orientation, fire_direction),
+                    priority: *priority,
+                    cooldown: cooldown.map(|w| (w.0.elapsed_secs(), w.0.duration().as_secs_f32())),
+                    preplaced: preplaced.copied(),
+                };
+                Some((entity, saved))
+            },
+        )
+        .unzip();
+
+    let (puddle_entities, puddles): (Vec<_>, Vec<_>) = puddles
+        .iter()
+        .map(
+            |(entity, Puddle(liquid), transform, velocity, potency, lifetime)| {
+                (
+                    entity,
+                    saved_liquid(*liquid, transform, velocity, potency, lifetime),
+                )
+            },
+        )
+        .unzip();
+
+    let sources = SourceIndex {
+        towers: tower_entities
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| (w, i))
+            .collect(),
+        puddles: puddle_entities
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| (w, i))
+            .collect(),
+        parents: &parents,
+    };
+
+    let enemies = enemies
+        .iter()
+        .filter_map(|entity| {
+            let kind = entity.get::<EnemyKind>()?;
+            let transform = entity.get::<Transform>()?;
+            Some(SavedEnemy {
+                kind: *kind,
+                translation: transform.translation.to_array(),
+                velocity: entity
+                    .get::<LinearVelocity>()
+                    .map(|w| w.0.to_array())
+                    .unwrap_or_default(),
+                health: entity.get::<EnemyHealth>()?.current(),
+                progress: entity
+                    .get::<PathProgress>()
+                    .map(|w| w.0)
+                    .unwrap_or_default(),
+                statuses: saved_statuses(&entity, &sources),
+                blown: entity.get::<Blown>().map(|w| w.0.elapsed_secs()),
+            })
+        })
+        .collect();
+
+    let droplets = droplets
+        .iter()
+        .map(|(Droplet(liquid), transform, velocity, potency)| {
+            saved_liquid(*liquid, transform, velocity, potency, None)
+        })
+        .collect();
+
+    // Shots from a tower that has since been sold are let go
+    let projectiles = projectiles
+        .iter()
+        .filter_map(|(projectile, transform, sprite, velocity, lifetime)| {
+            let Some(SavedSource::Tower(tower)) = sources.find(Some(projectile.tower)) else {
+                return None;
+            };
+            let color = sprite.color.to_srgba();
+            Some(SavedProjectile {
+                tower,
+                effects: projectile.effects.clone(),
+                color: [color.red, color.green, color.blue, color.alpha],
+                translation: transform.translation.to_array(),
+                velocity: velocity.map(|w| w.0.to_array()).unwrap_or_default(),
+                lifetime: (
+                    lifetime.0.elapsed_secs(),
+                    lifetime.0.duration().as_secs_f32(),
+                ),
+            })
+        })
+        .collect();
+
+    let total_waves = level_waves(&level_data, level.0).len();
+    let run = RunSave {
+        version: RUN_VERSION,
+        level: level.0,
+        player_state: *player_state,
+        waves: wave_manager.progress(total_waves),
+        economy: economy.clone(),
+        towers,
+        enemies,
+        droplets,
+        puddles,
+        projectiles,
+    };
+    persistence::save(RUN_KEY, &run);
+}
+
+// Waits for the level to be built, then puts everything back where it was
+fn resume_run(
+    resume: Res<ResumeRun>,
+    level_data: Res<LevelData>,
+    pieces: Query<(Entity, &Adjacent)>,
+    start: Query<&Transform, With<StartNode>>,
+    mut player_state: ResMut<PlayerState>,
+    mut wave_manager: ResMut<WaveManager>,
+    mut economy: ResMut<Economy>,
+    mut commands: Commands,
+) {
+    let Ok(start) = start.single() else {
+        return;
+    };
+    if pieces.is_empty() {
+        return;
+    }
+    let run = &resume.0;
+
+    *player_state = run.player_state;
+    *wave_manager = WaveManager::restore(level_waves(&level_data, run.level), run.waves);
+    *economy = run.economy.clone();
+
+    for (i, saved) in run.towers.iter().enumerate() {
+        let Some((piece, _)) = saved.placement.find_piece(pieces.iter()) else {
+            continue;
+        };
+        let snapshot = TowerSnapshot {
+            tower: saved.placement.tower,
+            piece,
+            orientation: saved.placement.orientation,
+            fan_direction: saved.placement.fan_direction,
+            priority: saved.priority,
+            preplaced: saved.preplaced,
+        };
+        let cooldown = match saved.cooldown {
+            Some((elapsed, duration)) => {
+                let mut cooldown = Cooldown::new(duration);
+                cooldown.0.set_elapsed(Duration::from_secs_f32(elapsed));
+                cooldown.store()
+            }
+            None => ().store(),
+        };
+        let restored = RestoredFrom(SavedSource::Tower(i)).store();
+        spawn_tower_with(&mut commands, snapshot, cooldown + restored);
+    }
+
+    for saved in &run.droplets {
+        commands.compose(
+            droplet(saved.liquid)
+                + (
+                    Transform::from_translation(Vec3::from_array(saved.translation)),
+                    LinearVelocity(Vec2::from_array(saved.velocity)),
+                    saved.potency,
+                )
+                    .store(),
+        );
+    }
+
+    for (i, saved) in run.puddles.iter().enumerate() {
+        let lifetime = match saved.lifetime {
+            Some(lifetime) => restored_lifetime(lifetime).store(),
+            None => ().store(),
+        };
+        commands.compose(
+            puddle(saved.liquid)
+                + (
+                    Transform::from_translation(Vec3::from_array(saved.translation)),
+                    LinearVelocity(Vec2::from_array(saved.velocity)),
+                    saved.potency,
+                    RestoredFrom(SavedSource::Puddle(i)),
+                )
+                    .store()
+                + lifetime,
+        );
+    }
+
+    // Projectiles need their tower, which only exists once these commands are applied
+    for saved in &run.projectiles {
+        commands.spawn(saved.clone());
+    }
+
+    for saved in &run.enemies {
+        let transform = Transform {
+            translation: Vec3::from_array(saved.translation),
+            ..*start
+        };
+        commands.compose(saved.kind.prefab() + (transform, saved.clone()).store());
+    }
+
+    commands.remove_resource::<ResumeRun>();
+    discard_saved_run();
+}
+
+fn restored_sources(restored: &Query<(Entity, &RestoredFrom)>) -> HashMap<SavedSource, Entity> {
+    restored.iter().map(|(entity, w)| (w.0, entity)).collect()
+}
+
+// Restored enemies come out of their prefab at full health, this catches them up
+fn apply_restored_enemies(
+    mut enemies: Query<(
+        Entity,
+        &SavedEnemy,
+        &mut EnemyHealth,
+        &mut PathProgress,
+        Option<&mut LinearVelocity>,
+    )>,
+    restored: Query<(Entity, &RestoredFrom)>,
+    children: Query<&Children>,
+    mut health_bars: Query<&mut Transform, With<EnemyHealthBar>>,
+    mut commands: Commands,
+) {
+    if enemies.is_empty() {
+        return;
+    }
+    let sources = restored_sources(&restored);
+
+    for (entity, saved, mut health, mut progress, velocity) in enemies.iter_mut() {
+        health.set_current(saved.health);
+        let fraction = health.current() as f32 / health.max() as f32;
+        let bar = DescendantIter::new(&children, entity).find(|w| health_bars.contains(*w));
+        if let Some(mut bar) = bar.and_then(|w| health_bars.get_mut(w).ok()) {
+            bar.scale.x = fraction;
+            bar.translation.x = -(HEALTH_BAR_WIDTH * (1.0 - fraction)) / 2.0;
+        }
+
+        progress.0 = saved.progress;
+        if let Some(mut velocity) = velocity {
+            velocity.0 = Vec2::from_array(saved.velocity);
+        }
+        let mut entity = commands.entity(entity);
+        for status in &saved.statuses {
+            let source = status.source.and_then(|w| sources.get(&w)).copied();
+            insert_status(&mut entity, status, source);
+        }
+        if let Some(elapsed) = saved.blown {
+            let mut blown = Blown::new();
+            blown.0.set_elapsed(Duration::from_secs_f32(elapsed));
+            entity.insert(blown);
+        }
+        entity.remove::<SavedEnemy>();
+    }
+}
+
+fn apply_restored_projectiles(
+    saved_projectiles: Query<(Entity, &SavedProjectile)>,
+    restored: Query<(Entity, &RestoredFrom)>,
+    parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    if saved_projectiles.is_empty() {
+        return;
+    }
+    let sources = restored_sources(&restored);
+
+    for (entity, saved) in saved_projectiles.iter() {
+        commands.entity(entity).despawn();
+        let Some(tower) = sources.get(&SavedSource::Tower(saved.tower)).copied() else {
+            continue;
+        };
+        let Ok(ChildOf(mount)) = parents.get(tower) else {
+            continue;
+        };
+        let [red, green, blue, alpha] = saved.color;
+        let (elapsed, duration) = saved.lifetime;
+        commands.compose(
+            projectile(
+                Projectile {
+                    tower,
+                    mount: *mount,
+                    effects: saved.effects.clone(),
+                },
+                Vec2::from_array(saved.velocity),
+                Color::srgba(red, green, blue, alpha),
+                duration,
+            ) + (
+                Transform::from_translation(Vec3::from_array(saved.translation)),
+                restored_lifetime((elapsed, duration)),
+            )
+                .store(),
+        );
+    }
+}
+
+fn forget_restored_sources(restored: Query<Entity, With<RestoredFrom>>, mut commands: Commands) {
+    for entity in restored.iter() {
+        commands.entity(entity).remove::<RestoredFrom>();
+    }
+}
@@ -11,7 +11,10 @@ use crate::{
     PausableSystems,
     data::{
         projectiles::DamageType,
-        stats::{DamageMultiplier, MoveSpeed, Stat, StatFriction, StatTrait},
+        stats::{
+            AttackDamage, AttackStrength, DamageMultiplier, FireRate, MoveSpeed, PushForce, Stat,
+            StatFriction, StatTrait, TriggerRange,
+        },
     },
     screens::Screen,
 };
@@ -31,6 +34,11 @@ pub(super) fn plugin(app: &mut App) {
 
     implement_stat::<MoveSpeed>(app);
     implement_stat::<StatFriction>(app);
+    implement_stat::<FireRate>(app);
+    implement_stat::<AttackDamage>(app);
+    implement_stat::<PushForce>(app);
+    implement_stat::<TriggerRange>(app);
+    implement_stat::<AttackStrength>(app);
     implement_stat::<DamageMultiplier<{ DamageType::Physical }>>(app);
     implement_stat::<DamageMultiplier<{ DamageType::Cold }>>(app);
     implement_stat::<DamageMultiplier<{ DamageType::Burning }>>(app);
@@ -0,0 +1,61 @@
+use bevy::ecs::{event::EventWriter, observer::Trigger, query::With, system::Query, world::OnAdd};
+
+use crate::{
+    data::status_effects::{Acidified, Oiled, Reaction, StatusEffect, StatusEnum, Wet},
+    demo::enemy_health::EnemyHealth,
+};
+
+use super::common::{ReactionTriggered, RemoveStatus, TryApplyStatus};
+
+pub fn poison_when_acidified(
+    trigger: Trigger<OnAdd, StatusEffect<Acidified>>,
+    oiled_enemies: Query<&StatusEffect<Oiled>, With<EnemyHealth>>,
+    acidified_enemies: Query<&StatusEffect<Acidified>>,
+    mut statuses: EventWriter<TryApplyStatus>,
+    mut reactions: EventWriter<ReactionTriggered>,
+    mut oiled: EventWriter<RemoveStatus<Oiled>>,
+) {
+    let e = trigger.target();
+    if let Ok(status) = oiled_enemies.get(e) {
+        let source = acidified_enemies.get(e).ok().and_then(|w| w.source);
+        statuses.write(TryApplyStatus {
+            status: StatusEnum::Poisoned,
+            enemy: e,
+            strength: status.strength + 1,
+            source,
+        });
+        reactions.write(ReactionTriggered {
+            reaction: Reaction::Poison,
+            enemy: e,
+            source,
+        });
+        oiled.write(RemoveStatus::new(e, status.strength, status.source));
+    }
+}
+
+pub fn slick_when_wet(
+    trigger: Trigger<OnAdd, StatusEffect<Wet>>,
+    oiled_enemies: Query<&StatusEffect<Oiled>, With<EnemyHealth>>,
+    wet_enemies: Query<&StatusEffect<Wet>>,
+    mut statuses: EventWriter<TryApplyStatus>,
+    mut reactions: EventWriter<ReactionTriggered>,
+    mut oiled: EventWriter<RemoveStatus<Oiled>>,
+) {
+    let e = trigger.target();
+    if let Ok(status) = oiled_enemies.get(e) {
+        let source = wet_enemies.get(e).ok().and_then(|w| w.source);
+        statuses.write(TryApplyStatus {
+            status: StatusEnum::Slippery,
+            enemy: e,
+            strength: status.strength,
+            source,
+        });
+        reactions.write(ReactionTriggered {
+            reaction: Reaction::Slick,
+            enemy: e,
+            source,
+        });
+        // The water stays on, so wet enemies still conduct for tesla turrets
+        oiled.write(RemoveStatus::new(e, status.strength, status.source));
+    }
+}
@@ -4,10 +4,10 @@ use bevy::{
         event::{Event, EventReader, EventWriter},
         query::With,
         schedule::{IntoScheduleConfigs, ScheduleConfigs},
-        system::{Commands, Local, Query, Res, ScheduleSystem},
+        system::{Commands, Query, Res, ScheduleSystem},
     },
     reflect::Reflect,
-    time::{Time, Timer},
+    time::{Time, Timer, TimerMode},
 };
 use std::marker::PhantomData;
 
@@ -15,7 +15,8 @@ use crate::{
     data::{
         stats::{Stat, StatTrait},
         status_effects::{
-            StatusEffect, StatusEffectTrait, StatusEnum, damage_multiplier, duration_multiplier,
+            DebuffStat, Reaction, StatusEffect, StatusEffectTrait, StatusEnum, damage_multiplier,
+            duration_multiplier, status_debuff,
         },
     },
     demo::enemy_health::{EnemyHealth, TryDamageToEnemy},
@@ -28,12 +29,21 @@ pub struct TryApplyStatus {
     pub status: StatusEnum,
     pub enemy: Entity,
     pub strength: usize,
+    pub source: Option<Entity>,
+}
+
+#[derive(Reflect, Debug, Event, PartialEq, Eq, Clone, Copy)]
+pub struct ReactionTriggered {
+    pub reaction: Reaction,
+    pub enemy: Entity,
+    pub source: Option<Entity>,
 }
 
 #[derive(Reflect, Debug, Event, PartialEq, Eq)]
 pub struct ApplyStatus<T: StatusEffectTrait> {
     pub enemy: Entity,
     pub strength: usize,
+    pub source: Option<Entity>,
     #[reflect(ignore)]
     _phantom: PhantomData<T>,
 }
@@ -42,37 +52,54 @@ pub struct ApplyStatus<T: StatusEffectTrait> {
 pub struct RemoveStatus<T: StatusEffectTrait> {
     pub enemy: Entity,
     pub strength: usize,
+    pub source: Option<Entity>,
     #[reflect(ignore)]
     _phantom: PhantomData<T>,
 }
 
-#[derive(Reflect, Debug, Event, PartialEq, Eq)]
-pub struct DOTTimer(pub Timer);
-
-impl Default for DOTTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(0.5, bevy::time::TimerMode::Once))
-    }
+pub fn periodic_damage<T: StatusEffectTrait>(dps: isize) -> ScheduleConfigs<ScheduleSystem> {
+    (move |mut enemies: Query<(Entity, &mut StatusEffect<T>), With<EnemyHealth>>,
+           time: Res<Time>,
+           mut damage_events: EventWriter<TryDamageToEnemy>| {
+        for (enemy, mut effect) in enemies.iter_mut() {
+            let damage = effect.accumulate_dot(time.delta(), dps as f32);
+            if damage > 0 {
+                damage_events.write(TryDamageToEnemy {
+                    damage,
+                    damage_type: T::damage_element(),
+                    enemy: enemy,
+                    strength: effect.strength,
+                    source: effect.source,
+                    status: Some(T::corresponding_enum()),
+                });
+            }
+        }
+    })
+    .into_configs()
 }
 
-pub fn periodic_damage<T: StatusEffectTrait>(dps: isize) -> ScheduleConfigs<ScheduleSystem> {
-    let damage_per_tick = dps / 2;
-    (move |enemies: Query<(Entity, &StatusEffect<T>), With<EnemyHealth>>,
-           mut cooldown: Local<DOTTimer>,
+// Damage over time that grows from `start_dps` to `end_dps` as the effect runs out
+pub fn ramping_damage<T: StatusEffectTrait>(
+    start_dps: isize,
+    end_dps: isize,
+) -> ScheduleConfigs<ScheduleSystem> {
+    (move |mut enemies: Query<(Entity, &mut StatusEffect<T>), With<EnemyHealth>>,
            time: Res<Time>,
            mut damage_events: EventWriter<TryDamageToEnemy>| {
-        cooldown.0.tick(time.delta());
-        if (*cooldown).0.just_finished() {
-            for (enemy, effect) in enemies.iter() {
+        for (enemy, mut effect) in enemies.iter_mut() {
+            let progress = effect.duration.fraction();
+            let dps = start_dps as f32 + (end_dps - start_dps) as f32 * progress;
+            let damage = effect.accumulate_dot(time.delta(), dps);
+            if damage > 0 {
                 damage_events.write(TryDamageToEnemy {
-                    damage: (damage_per_tick),
+                    damage,
                     damage_type: T::damage_element(),
                     enemy: enemy,
                     strength: effect.strength,
+                    source: effect.source,
+                    status: Some(T::corresponding_enum()),
                 });
             }
-            cooldown.0.reset();
-            cooldown.0.unpause();
         }
     })
     .into_configs()
@@ -81,7 +108,7 @@ pub fn periodic_damage<T: StatusEffectTrait>(dps: isize) -> ScheduleConfigs<Sche
 pub fn status_debuff_premul_flat<S: StatusEffectTrait, T: StatTrait>(
     debuff: f32,
 ) -> ScheduleConfigs<ScheduleSystem> {
-    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>), With<EnemyHealth>>| {
+    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>)>| {
         for (mut stat, status) in enemies.iter_mut() {
             stat.premul_bonus(debuff * damage_multiplier(status.strength));
         }
@@ -92,7 +119,7 @@ pub fn status_debuff_premul_flat<S: StatusEffectTrait, T: StatTrait>(
 pub fn status_debuff_multiplier<S: StatusEffectTrait, T: StatTrait>(
     debuff: f32,
 ) -> ScheduleConfigs<ScheduleSystem> {
-    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>), With<EnemyHealth>>| {
+    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>)>| {
         for (mut stat, status) in enemies.iter_mut() {
             stat.multiplier(debuff);
         }
@@ -100,10 +127,17 @@ pub fn status_debuff_multiplier<S: StatusEffectTrait, T: StatTrait>(
     .into_configs()
 }
 
+// Like `status_debuff_multiplier`, with the multiplier looked up in `STATUS_DEBUFFS`
+pub fn table_debuff<S: StatusEffectTrait, T: StatTrait>(
+    stat: DebuffStat,
+) -> ScheduleConfigs<ScheduleSystem> {
+    status_debuff_multiplier::<S, T>(status_debuff(S::corresponding_enum(), stat))
+}
+
 pub fn status_debuff_postmul_flat<S: StatusEffectTrait, T: StatTrait>(
     debuff: f32,
 ) -> ScheduleConfigs<ScheduleSystem> {
-    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>), With<EnemyHealth>>| {
+    (move |mut enemies: Query<(&mut Stat<T>, &StatusEffect<S>)>| {
         for (mut stat, status) in enemies.iter_mut() {
             stat.postmul_bonus(debuff * damage_multiplier(status.strength));
         }
@@ -119,33 +153,51 @@ pub fn dispatch_typed_events<T: StatusEffectTrait>(
         status,
         enemy,
         strength,
+        source,
     } in reader
         .read()
         .filter(|w| w.status == T::corresponding_enum())
     {
-        writer.write(ApplyStatus::new(*enemy, *strength));
+        writer.write(ApplyStatus::new(*enemy, *strength, *source));
     }
 }
 
+// Reapplying refreshes an effect in place, so its damage over time keeps ticking
 pub fn apply_status_effects<T: StatusEffectTrait>(
     mut events: EventReader<ApplyStatus<T>>,
+    mut effects: Query<&mut StatusEffect<T>>,
     mut commands: Commands,
 ) {
     for ApplyStatus {
         enemy,
         strength,
+        source,
         _phantom,
     } in events.read()
     {
-        commands.entity(*enemy).insert(StatusEffect::<T>::new(
-            *strength,
-            T::base_duration() * duration_multiplier(*strength),
-        ));
+        if let Ok(mut effect) = effects.get_mut(*enemy) {
+            effect.strength = effect.strength.max(*strength);
+            effect.duration = Timer::from_seconds(
+                T::base_duration() * duration_multiplier(effect.strength),
+                TimerMode::Once,
+            );
+            if source.is_some() {
+                effect.source = *source;
+            }
+            continue;
+        }
+        commands.entity(*enemy).insert(
+            StatusEffect::<T>::new(
+                *strength,
+                T::base_duration() * duration_multiplier(*strength),
+            )
+            .with_source(*source),
+        );
     }
 }
 
 pub fn tick_statuses<T: StatusEffectTrait>(
-    mut enemies: Query<&mut StatusEffect<T>, With<EnemyHealth>>,
+    mut enemies: Query<&mut StatusEffect<T>>,
     time: Res<Time>,
 ) {
     for mut enemy in enemies.iter_mut() {
@@ -159,7 +211,7 @@ pub fn timeout_statuses<T: StatusEffectTrait>(
 ) {
     for (enemy, status) in enemies.iter() {
         if status.duration.finished() {
-            events.write(RemoveStatus::new(enemy, status.strength));
+            events.write(RemoveStatus::new(enemy, status.strength, status.source));
         }
     }
 }
@@ -169,9 +221,7 @@ pub fn do_remove_status<T: StatusEffectTrait>(
     mut commands: Commands,
 ) {
     for RemoveStatus {
-        enemy,
-        strength,
-        _phantom,
+        enemy, strength, ..
     } in events.read()
     {
         commands.entity(*enemy).remove::<StatusEffect<T>>();
@@ -179,20 +229,22 @@ pub fn do_remove_status<T: StatusEffectTrait>(
 }
 
 impl<T: StatusEffectTrait> ApplyStatus<T> {
-    pub fn new(enemy: Entity, strength: usize) -> ApplyStatus<T> {
+    pub fn new(enemy: Entity, strength: usize, source: Option<Entity>) -> ApplyStatus<T> {
         ApplyStatus {
             enemy,
             strength,
+            source,
             _phantom: PhantomData,
         }
     }
 }
 
 impl<T: StatusEffectTrait> RemoveStatus<T> {
-    pub fn new(enemy: Entity, strength: usize) -> RemoveStatus<T> {
+    pub fn new(enemy: Entity, strength: usize, source: Option<Entity>) -> RemoveStatus<T> {
         RemoveStatus {
             enemy,
             strength,
+            source,
             _phantom: PhantomData,
         }
     }
@@ -54,17 +54,15 @@ pub fn add_status_animation<T: StatusEffectTrait>(
     for event in events.read() {
         let e = event.enemy;
 
-        // Get the entity that holds the enemy's sprite
+        // Get the entity that holds the enemy's sprite, towers hold their own
         let enemy_sprite_entity = children
             .get(e)
-            .unwrap()
-            .iter()
-            .filter(|w| enemy_sprites.get(**w).is_ok())
-            .next()
-            .unwrap();
+            .ok()
+            .and_then(|w| w.iter().find(|c| enemy_sprites.get(**c).is_ok()).copied())
+            .unwrap_or(e);
 
         // Get rid of any existing Entity in charge of representing this status effect
-        if let Ok(children_) = children.get(*enemy_sprite_entity) {
+        if let Ok(children_) = children.get(enemy_sprite_entity) {
             for entity in children_
                 .iter()
                 .filter(|w| existing_status_animations.get(**w).is_ok())
@@ -75,7 +73,7 @@ pub fn add_status_animation<T: StatusEffectTrait>(
 
         let status_sprite_bundle = sprites.status_bundle(T::corresponding_enum());
 
-        commands.entity(*enemy_sprite_entity).with_children(|p| {
+        commands.entity(enemy_sprite_entity).with_children(|p| {
             p.spawn((
                 StatusAnimation::<T>::new(),
                 status_sprite_bundle,
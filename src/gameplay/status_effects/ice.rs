@@ -1,25 +1,34 @@
 use bevy::ecs::{event::EventWriter, observer::Trigger, query::With, system::Query, world::OnAdd};
 
 use crate::{
-    data::status_effects::{Chilled, Frozen, StatusEffect, StatusEnum, Wet},
+    data::status_effects::{Chilled, Frozen, Reaction, StatusEffect, StatusEnum, Wet},
     demo::enemy_health::EnemyHealth,
 };
 
-use super::common::{RemoveStatus, TryApplyStatus};
+use super::common::{ReactionTriggered, RemoveStatus, TryApplyStatus};
 
 pub fn freeze_when_wet(
     trigger: Trigger<OnAdd, StatusEffect<Chilled>>,
     wet_enemies: Query<&StatusEffect<Wet>, With<EnemyHealth>>,
+    chilled_enemies: Query<&StatusEffect<Chilled>>,
     mut frozen_statuses: EventWriter<TryApplyStatus>,
+    mut reactions: EventWriter<ReactionTriggered>,
     mut wet_statuses: EventWriter<RemoveStatus<Wet>>,
 ) {
     let e = trigger.target();
     if let Ok(status) = wet_enemies.get(e) {
+        let source = chilled_enemies.get(e).ok().and_then(|w| w.source);
         frozen_statuses.write(TryApplyStatus {
             status: StatusEnum::Frozen,
             enemy: e,
             strength: 2,
+            source,
         });
-        wet_statuses.write(RemoveStatus::new(e, status.strength));
+        reactions.write(ReactionTriggered {
+            reaction: Reaction::Freeze,
+            enemy: e,
+            source,
+        });
+        wet_statuses.write(RemoveStatus::new(e, status.strength, status.source));
     }
 }
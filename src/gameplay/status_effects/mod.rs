@@ -5,31 +5,34 @@ use bevy::{
     state::condition::in_state,
     time::common_conditions::on_timer,
 };
+use chemical::{poison_when_acidified, slick_when_wet};
 use common::{
-    ApplyStatus, RemoveStatus, TryApplyStatus, apply_status_effects, dispatch_typed_events,
-    do_remove_status, periodic_damage, status_debuff_multiplier, tick_statuses, timeout_statuses,
+    ApplyStatus, ReactionTriggered, RemoveStatus, TryApplyStatus, apply_status_effects,
+    dispatch_typed_events, do_remove_status, periodic_damage, ramping_damage, table_debuff,
+    tick_statuses, timeout_statuses,
 };
 use display::{add_status_animation, animate_status_effect, remove_status_animation_on_timeout};
 use fire::ignite_when_burned;
 use ice::freeze_when_wet;
 use std::time::Duration;
-use tesla::{damage_after_electrocute, electrocute_on_damage};
+use tesla::{damage_after_electrocute, electrocute_on_damage, magnetic_pull};
 
 use crate::{
     PausableSystems,
     data::{
         projectiles::DamageType,
-        stats::{DamageMultiplier, DamageMultiplierAll, MoveSpeed, StatFriction},
+        stats::{DamageMultiplier, DamageMultiplierAll, FireRate, MoveSpeed, StatFriction},
         status_effects::{
-            Acidified, Burned, Chilled, Electrocuted, Frozen, Ignited, Oiled, StatusEffect,
-            StatusEffectTrait, Wet,
+            Acidified, Burned, Chilled, DebuffStat, Electrocuted, Frozen, Ignited, Magnetized,
+            Oiled, Poisoned, Slippery, StatusEffect, StatusEffectTrait, Stunned, Wet,
         },
     },
     screens::Screen,
 };
 
-use super::stats::StatSet;
+use super::{combat_log::record_status_removal, stats::StatSet};
 
+pub mod chemical;
 pub mod common;
 pub mod display;
 pub mod fire;
@@ -38,7 +41,9 @@ pub mod tesla;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<TryApplyStatus>()
-        .add_event::<TryApplyStatus>();
+        .register_type::<ReactionTriggered>()
+        .add_event::<TryApplyStatus>()
+        .add_event::<ReactionTriggered>();
 
     app.add_systems(
         FixedUpdate,
@@ -47,6 +52,7 @@ pub(super) fn plugin(app: &mut App) {
             periodic_damage::<Burned>(4),
             periodic_damage::<Acidified>(4),
             periodic_damage::<Chilled>(3),
+            ramping_damage::<Poisoned>(2, 16),
         )
             .in_set(PausableSystems)
             .run_if(in_state(Screen::Gameplay)),
@@ -54,9 +60,15 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_observer(freeze_when_wet);
     app.add_observer(ignite_when_burned);
+    app.add_observer(poison_when_acidified);
+    app.add_observer(slick_when_wet);
     app.add_systems(
         Update,
-        (damage_after_electrocute, electrocute_on_damage)
+        (
+            damage_after_electrocute,
+            electrocute_on_damage,
+            magnetic_pull,
+        )
             .in_set(PausableSystems)
             .run_if(in_state(Screen::Gameplay)),
     );
@@ -64,18 +76,34 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            status_debuff_multiplier::<Frozen, MoveSpeed>(0.),
-            status_debuff_multiplier::<Frozen, StatFriction>(0.),
-            status_debuff_multiplier::<Frozen, DamageMultiplier<{ DamageType::Physical }>>(4.),
-            status_debuff_multiplier::<Chilled, MoveSpeed>(0.5),
-            status_debuff_multiplier::<Wet, MoveSpeed>(0.9),
-            status_debuff_multiplier::<Wet, DamageMultiplier<{ DamageType::Lightning }>>(1.5),
-            status_debuff_multiplier::<Oiled, StatFriction>(0.),
-            status_debuff_multiplier::<Burned, DamageMultiplierAll>(1.10),
-            status_debuff_multiplier::<Burned, DamageMultiplier<{ DamageType::Cold }>>(1.35),
-            status_debuff_multiplier::<Acidified, DamageMultiplierAll>(1.05),
-            status_debuff_multiplier::<Acidified, DamageMultiplier<{ DamageType::Burning }>>(1.10),
-            status_debuff_multiplier::<Acidified, DamageMultiplier<{ DamageType::Chemical }>>(1.10),
+            table_debuff::<Frozen, MoveSpeed>(DebuffStat::MoveSpeed),
+            table_debuff::<Frozen, StatFriction>(DebuffStat::Friction),
+            table_debuff::<Frozen, DamageMultiplier<{ DamageType::Physical }>>(
+                DebuffStat::DamageTaken(DamageType::Physical),
+            ),
+            table_debuff::<Chilled, MoveSpeed>(DebuffStat::MoveSpeed),
+            table_debuff::<Wet, MoveSpeed>(DebuffStat::MoveSpeed),
+            table_debuff::<Wet, DamageMultiplier<{ DamageType::Lightning }>>(
+                DebuffStat::DamageTaken(DamageType::Lightning),
+            ),
+            table_debuff::<Oiled, StatFriction>(DebuffStat::Friction),
+            table_debuff::<Burned, DamageMultiplierAll>(DebuffStat::AllDamageTaken),
+            table_debuff::<Burned, DamageMultiplier<{ DamageType::Cold }>>(
+                DebuffStat::DamageTaken(DamageType::Cold),
+            ),
+            table_debuff::<Acidified, DamageMultiplierAll>(DebuffStat::AllDamageTaken),
+            table_debuff::<Acidified, DamageMultiplier<{ DamageType::Burning }>>(
+                DebuffStat::DamageTaken(DamageType::Burning),
+            ),
+            table_debuff::<Acidified, DamageMultiplier<{ DamageType::Chemical }>>(
+                DebuffStat::DamageTaken(DamageType::Chemical),
+            ),
+            table_debuff::<Stunned, MoveSpeed>(DebuffStat::MoveSpeed),
+            table_debuff::<Slippery, StatFriction>(DebuffStat::Friction),
+            // Only towers have a fire rate, so these are debuffs from saboteur enemies
+            table_debuff::<Frozen, FireRate>(DebuffStat::FireRate),
+            table_debuff::<Electrocuted, FireRate>(DebuffStat::FireRate),
+            table_debuff::<Wet, FireRate>(DebuffStat::FireRate),
         )
             .in_set(StatSet::Modify)
             .in_set(PausableSystems)
@@ -90,6 +118,10 @@ pub(super) fn plugin(app: &mut App) {
     implement_status_effect::<Electrocuted>(app);
     implement_status_effect::<Acidified>(app);
     implement_status_effect::<Oiled>(app);
+    implement_status_effect::<Stunned>(app);
+    implement_status_effect::<Poisoned>(app);
+    implement_status_effect::<Magnetized>(app);
+    implement_status_effect::<Slippery>(app);
 }
 
 pub fn implement_status_effect<T: StatusEffectTrait>(app: &mut App) {
@@ -105,6 +137,7 @@ pub fn implement_status_effect<T: StatusEffectTrait>(app: &mut App) {
                 tick_statuses::<T>,
                 timeout_statuses::<T>,
                 do_remove_status::<T>,
+                record_status_removal::<T>,
                 remove_status_animation_on_timeout::<T>,
             )
                 .in_set(PausableSystems)
@@ -1,20 +1,23 @@
+use avian2d::prelude::ExternalImpulse;
 use bevy::{
     ecs::{
         entity::Entity,
         event::{EventReader, EventWriter},
         query::With,
-        system::{Query, ResMut},
+        system::{Query, Res, ResMut},
     },
+    math::Vec3Swizzles,
+    time::Time,
     transform::components::Transform,
 };
 use bevy_turborand::{DelegatedRng, GlobalRng};
 
-use super::common::{RemoveStatus, TryApplyStatus};
+use super::common::{ReactionTriggered, RemoveStatus, TryApplyStatus};
 use crate::{
     data::{
         StatusEffect,
         projectiles::DamageType,
-        status_effects::{Electrocuted, StatusEnum, Wet, damage_multiplier},
+        status_effects::{Electrocuted, Magnetized, Reaction, StatusEnum, Wet, damage_multiplier},
     },
     demo::enemy_health::{DoDamageToEnemy, EnemyHealth, TryDamageToEnemy},
 };
@@ -30,6 +33,8 @@ pub fn electrocute_on_damage(
         damage_type,
         enemy,
         strength,
+        source,
+        ..
     } in damage_events.read()
     {
         if *damage_type == DamageType::Lightning {
@@ -41,6 +46,7 @@ pub fn electrocute_on_damage(
                     status: StatusEnum::Electrocuted,
                     enemy: *enemy,
                     strength: 1.max(strength - 1),
+                    source: *source,
                 });
             }
         }
@@ -50,13 +56,22 @@ pub fn electrocute_on_damage(
 pub fn damage_after_electrocute(
     mut electro_events: EventReader<RemoveStatus<Electrocuted>>,
     mut damage_events: EventWriter<TryDamageToEnemy>,
+    mut reactions: EventWriter<ReactionTriggered>,
     enemies: Query<(Entity, &Transform), With<EnemyHealth>>,
 ) {
     for RemoveStatus {
-        enemy, strength, ..
+        enemy,
+        strength,
+        source,
+        ..
     } in electro_events.read()
     {
         if let Ok((_, source_pos)) = enemies.get(*enemy) {
+            reactions.write(ReactionTriggered {
+                reaction: Reaction::ChainLightning,
+                enemy: *enemy,
+                source: *source,
+            });
             let source_loc = source_pos.translation.clone();
             for (near_enemy, _) in enemies
                 .iter()
@@ -67,9 +82,31 @@ pub fn damage_after_electrocute(
                     strength: *strength,
                     damage_type: DamageType::Lightning,
                     enemy: near_enemy,
+                    source: *source,
+                    status: Some(StatusEnum::Electrocuted),
                 });
-                println!("ZANG");
             }
         }
     }
 }
+
+pub const MAGNET_RANGE: f32 = 15.;
+pub const MAGNET_FORCE: f32 = 30.;
+
+// Magnetized enemies drag their neighbours towards them, bunching them up for tesla chains
+pub fn magnetic_pull(
+    magnets: Query<(Entity, &Transform, &StatusEffect<Magnetized>)>,
+    mut enemies: Query<(Entity, &Transform, &mut ExternalImpulse), With<EnemyHealth>>,
+    time: Res<Time>,
+) {
+    let delta = time.delta().as_secs_f32();
+    for (magnet, magnet_pos, status) in magnets.iter() {
+        let magnet_loc = magnet_pos.translation.xy();
+        for (_, pos, mut impulse) in enemies.iter_mut().filter(|(e, pos, _)| {
+            *e != magnet && pos.translation.xy().distance(magnet_loc) < MAGNET_RANGE
+        }) {
+            let pull = (magnet_loc - pos.translation.xy()).normalize_or_zero();
+            **impulse += pull * MAGNET_FORCE * damage_multiplier(status.strength) * delta;
+        }
+    }
+}
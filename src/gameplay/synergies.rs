@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    data::{
+        PointerInteractionState, Tower,
+        stats::{AttackStrength, FireRate, Stat, TriggerRange},
+        synergies::{Synergy, SynergyBonus},
+    },
+    gameplay::{stats::StatSet, tower_placement::TowerPreview},
+    level::components::Adjacent,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ActiveSynergies>();
+    app.add_systems(
+        Update,
+        (
+            recompute_synergies,
+            apply_synergy_bonuses.in_set(StatSet::Modify),
+        )
+            .chain()
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        draw_synergy_links.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// Bonuses this tower currently receives, and which neighbor grants each one
+#[derive(Component, Debug, Default, Clone, Reflect)]
+pub struct ActiveSynergies(pub Vec<(Entity, SynergyBonus)>);
+
+// Towers are children of the wall/floor piece they're mounted on
+fn tower_cells(
+    towers: &Query<(Entity, &Tower, &ChildOf)>,
+    adjacents: &Query<&Adjacent>,
+) -> Vec<(Entity, Tower, IVec2)> {
+    towers
+        .iter()
+        .filter_map(|(e, tower, parent)| {
+            adjacents
+                .get(parent.parent())
+                .ok()
+                .map(|w| (e, *tower, w.cell()))
+        })
+        .collect()
+}
+
+fn recompute_synergies(
+    added: Query<(), Added<Tower>>,
+    mut removed: RemovedComponents<Tower>,
+    towers: Query<(Entity, &Tower, &ChildOf)>,
+    adjacents: Query<&Adjacent>,
+    mut commands: Commands,
+) {
+    // Only rebuild when a tower is placed or sold
+    if added.is_empty() && removed.read().count() == 0 {
+        return;
+    }
+
+    let cells = tower_cells(&towers, &adjacents);
+    for &(target, target_tower, target_cell) in &cells {
+        let bonuses = cells
+            .iter()
+            .filter(|(source, ..)| *source != target)
+            .flat_map(|&(source, source_tower, source_cell)| {
+                Synergy::between(source_tower, source_cell, target_tower, target_cell)
+                    .map(move |bonus| (source, bonus))
+            })
+            .collect();
+        commands.entity(target).insert(ActiveSynergies(bonuses));
+    }
+}
+
+fn apply_synergy_bonuses(
+    mut towers: Query<(
+        &ActiveSynergies,
+        &mut Stat<AttackStrength>,
+        &mut Stat<TriggerRange>,
+        &mut Stat<FireRate>,
+    )>,
+) {
+    for (synergies, mut strength, mut range, mut fire_rate) in towers.iter_mut() {
+        for (_, bonus) in &synergies.0 {
+            match *bonus {
+                SynergyBonus::Strength(amount) => strength.postmul_bonus(amount as f32),
+                SynergyBonus::TriggerRange(mul) => range.multiplier(mul),
+                SynergyBonus::FireRate(mul) => fire_rate.multiplier(mul),
+            }
+        }
+    }
+}
+
+// While placing, show the links that already exist and the ones the preview would make
+fn draw_synergy_links(
+    pointer_state: Res<State<PointerInteractionState>>,
+    preview: Res<TowerPreview>,
+    towers: Query<(Entity, &Tower, &ChildOf)>,
+    adjacents: Query<&Adjacent>,
+    synergies: Query<(&GlobalTransform, &ActiveSynergies)>,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    if !matches!(pointer_state.get(), PointerInteractionState::Placing(_)) {
+        return;
+    }
+
+    for (target_pos, active) in synergies.iter() {
+        for (source, bonus) in &active.0 {
+            if let Ok(source_pos) = transforms.get(*source) {
+                gizmos.line_2d(
+                    source_pos.translation().xy(),
+                    target_pos.translation().xy(),
+                    bonus.color().with_alpha(0.4),
+                );
+            }
+        }
+    }
+
+    let (Some(tower), Some(position_entity)) = (preview.tower, preview.position_entity) else {
+        return;
+    };
+    let (Ok(adjacent), Ok(preview_pos)) = (
+        adjacents.get(position_entity),
+        transforms.get(position_entity),
+    ) else {
+        return;
+    };
+    let preview_cell = adjacent.cell();
+    let preview_pos = preview_pos.translation().xy();
+
+    for (other, other_tower, other_cell) in tower_cells(&towers, &adjacents) {
+        let Ok(other_pos) = transforms.get(other) else {
+            continue;
+        };
+        let other_pos = other_pos.translation().xy();
+        for bonus in Synergy::between(tower, preview_cell, other_tower, other_cell).chain(
+            Synergy::between(other_tower, other_cell, tower, preview_cell),
+        ) {
+            gizmos.line_2d(preview_pos, other_pos, bonus.color());
+            gizmos.circle_2d(other_pos, 2., bonus.color());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+
+use crate::{
+    data::{PointerInteractionState, Tower, projectiles::TowerAttackType},
+    gameplay::towers::targeting::TargetPriority,
+    prelude::*,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SelectedTower>();
+    app.add_observer(select_tower_on_click);
+    app.add_systems(OnEnter(Screen::Gameplay), clear_selected_tower);
+    app.add_systems(
+        Update,
+        (close_panel_for_missing_tower, update_priority_label).run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+// The placed tower whose panel is open
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct SelectedTower(pub Option<Entity>);
+
+#[derive(Component)]
+struct TowerPanel;
+
+#[derive(Component)]
+struct PriorityLabel;
+
+fn clear_selected_tower(mut selected: ResMut<SelectedTower>) {
+    selected.0 = None;
+}
+
+fn select_tower_on_click(
+    trigger: Trigger<Pointer<Click>>,
+    pointer_state: Res<State<PointerInteractionState>>,
+    screen: Res<State<Screen>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    towers: Query<(Entity, &GlobalTransform, &Tower)>,
+    panels: Query<Entity, With<TowerPanel>>,
+    ui_nodes: Query<(), With<Node>>,
+    mut selected: ResMut<SelectedTower>,
+    mut commands: Commands,
+) {
+    // Clicks on the UI (including this panel) never reach the towers below
+    if ui_nodes.get(trigger.target()).is_ok()
+        || trigger.event().button != PointerButton::Primary
+        || *pointer_state.get() != PointerInteractionState::Selecting
+        || *screen.get() != Screen::Gameplay
+    {
+        return;
+    }
+
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    let Some(game_cursor_position) = window
+        .cursor_position()
+        .and_then(|w| camera.viewport_to_world_2d(camera_transform, w).ok())
+    else {
+        return;
+    };
+
+    let Some((entity, _, tower)) = towers
+        .iter()
+        .filter(|(_, transform, _)| {
+            transform.translation().xy().distance(game_cursor_position) < 5.0
+        })
+        .min_by(|a, b| {
+            let a = a.1.translation().xy().distance(game_cursor_position);
+            let b = b.1.translation().xy().distance(game_cursor_position);
+            a.total_cmp(&b)
+        })
+    else {
+        return;
+    };
+
+    if selected.0 == Some(entity) {
+        return;
+    }
+    for panel in panels.iter() {
+        commands.entity(panel).despawn();
+    }
+    selected.0 = Some(entity);
+    commands.spawn(tower_panel(*tower));
+}
+
+fn tower_panel(tower: Tower) -> impl Bundle {
+    let has_priority = matches!(tower.attack_def(), TowerAttackType::EntireCell(_));
+    (
+        Name::new("Tower Panel"),
+        TowerPanel,
+        StateScoped(Screen::Gameplay),
+        BorderRadius::all(Val::Px(8.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            top: Val::Px(80.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            row_gap: Val::Px(8.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.75)),
+        children![
+            widget::ui_font(tower.name()),
+            (
+                Node {
+                    column_gap: Val::Px(8.0),
+                    align_items: AlignItems::Center,
+                    display: if has_priority {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    ..default()
+                },
+                children![
+                    widget::button_small("<", prev_priority),
+                    (widget::ui_font("Target: All"), PriorityLabel),
+                    widget::button_small(">", next_priority),
+                ],
+            ),
+            widget::button_small("X", close_panel),
+        ],
+    )
+}
+
+fn next_priority(
+    _: Trigger<Pointer<Click>>,
+    selected: Res<SelectedTower>,
+    mut priorities: Query<&mut TargetPriority>,
+) {
+    if let Some(mut priority) = selected.0.and_then(|w| priorities.get_mut(w).ok()) {
+        *priority = priority.next();
+    }
+}
+
+fn prev_priority(
+    _: Trigger<Pointer<Click>>,
+    selected: Res<SelectedTower>,
+    mut priorities: Query<&mut TargetPriority>,
+) {
+    if let Some(mut priority) = selected.0.and_then(|w| priorities.get_mut(w).ok()) {
+        *priority = priority.prev();
+    }
+}
+
+fn close_panel(
+    _: Trigger<Pointer<Click>>,
+    panels: Query<Entity, With<TowerPanel>>,
+    mut selected: ResMut<SelectedTower>,
+    mut commands: Commands,
+) {
+    for panel in panels.iter() {
+        commands.entity(panel).despawn();
+    }
+    selected.0 = None;
+}
+
+// Selling a tower closes its panel
+fn close_panel_for_missing_tower(
+    towers: Query<(), With<Tower>>,
+    panels: Query<Entity, With<TowerPanel>>,
+    mut selected: ResMut<SelectedTower>,
+    mut commands: Commands,
+) {
+    let Some(tower) = selected.0 else {
+        return;
+    };
+    if towers.get(tower).is_err() {
+        for panel in panels.iter() {
+            commands.entity(panel).despawn();
+        }
+        selected.0 = None;
+    }
+}
+
+fn update_priority_label(
+    selected: Res<SelectedTower>,
+    priorities: Query<&TargetPriority>,
+    mut labels: Query<&mut Text, With<PriorityLabel>>,
+) {
+    let Some(priority) = selected.0.and_then(|w| priorities.get(w).ok()) else {
+        return;
+    };
+    for mut label in labels.iter_mut() {
+        let text = format!("Target: {}", priority.name());
+        if label.0 != text {
+            label.0 = text;
+        }
+    }
+}
@@ -1,18 +1,46 @@
 use std::time::Duration;
 
-use bevy::prelude::*;
-use bevy_composable::app_impl::{ComplexSpawnable, ComponentTreeable};
+use bevy::{
+    color::palettes::tailwind,
+    ecs::system::SystemParam,
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+use bevy_composable::{
+    app_impl::{ComplexSpawnable, ComponentTreeable},
+    tree::ComponentTree,
+};
 
 use crate::{
     assets::{SoundEffects, TowerSprites},
     audio::sound_effect,
-    data::*,
-    gameplay::{hotbar::HotbarItem, messages::DisplayFlashMessage},
+    data::{
+        placement::{
+            PlacedTower, PlacementContext, PlacementRule, PlacementVerdict, evaluate,
+            placement_rules,
+        },
+        *,
+    },
+    gameplay::{
+        action_history::{ActionHistory, TowerAction, TowerSnapshot},
+        build_queue::{BuildGhost, BuildQueue, queue_build},
+        grid_cursor::GridCursor,
+        hotbar::HotbarItem,
+        level_setup::Preplaced,
+        messages::DisplayFlashMessage,
+        towers::{
+            directional::FireDirection,
+            fan::{FanNeedsDirection, against_path},
+            targeting::TargetPriority,
+        },
+    },
+    input::{Action, Actions},
     level::{
-        components::{Adjacent, Ceiling, ExactPosition, Floor, LEVEL_SCALING, Wall, WallDirection},
+        components::{Adjacent, LEVEL_SCALING, PathNode},
         resource::CellDirection,
     },
     prelude::*,
+    theme::prelude::LABEL_FONT,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -37,7 +65,21 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.insert_resource(TowerPreview::default());
-    app.add_systems(Update, remove_preview);
+    app.insert_resource(FanAim::default());
+    app.init_resource::<PlacementRules>();
+    app.add_systems(
+        Update,
+        show_placement_verdict.run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        (rotate_placement, rotate_fan_aim, draw_fan_aim).run_if(in_state(Screen::Gameplay)),
+    );
+    // The grid cursor keeps its preview wherever the mouse is
+    app.add_systems(
+        Update,
+        remove_preview.run_if(resource_equals(GridCursor(None))),
+    );
     app.add_observer(observe_placeholder);
     app.add_observer(right_click_tower_options);
 
@@ -50,20 +92,88 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 #[derive(Event, Debug, Hash, PartialEq, Eq, Clone, Reflect)]
-enum TowerPlacementEvent {
+pub(crate) enum TowerPlacementEvent {
     Requested(Tower, Entity, CellDirection),
     Accepted(Tower, Entity, CellDirection),
 }
 
 #[derive(Resource, Debug, Default, Clone, Copy, Reflect)]
-struct TowerPreview {
-    tower: Option<Tower>,
-    position_entity: Option<Entity>,
-    cell_direction: Option<CellDirection>,
+pub(crate) struct TowerPreview {
+    pub(crate) tower: Option<Tower>,
+    pub(crate) position_entity: Option<Entity>,
+    pub(crate) cell_direction: Option<CellDirection>,
 }
 
 #[derive(Component, Debug, Default, Clone, Copy, Reflect)]
-struct SpawnedPreview;
+pub(crate) struct SpawnedPreview;
+
+// Shows why the previewed placement isn't allowed
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+struct PreviewReason;
+
+#[derive(Resource)]
+pub(crate) struct PlacementRules(pub(crate) Vec<Box<dyn PlacementRule>>);
+
+impl Default for PlacementRules {
+    fn default() -> Self {
+        Self(placement_rules())
+    }
+}
+
+fn placed_towers(
+    towers: &Query<(&ChildOf, &Tower)>,
+    pieces: &Query<(Entity, &Adjacent)>,
+) -> Vec<PlacedTower> {
+    towers
+        .iter()
+        .filter_map(|(ChildOf(parent), tower)| {
+            pieces.get(*parent).ok().map(|(_, piece)| PlacedTower {
+                tower: *tower,
+                piece: *piece,
+            })
+        })
+        .collect()
+}
+
+// A reserved spot belongs to the queued tower, whatever the rules say
+fn placement_verdict(
+    rules: &PlacementRules,
+    queue: &BuildQueue,
+    context: &PlacementContext,
+    piece: Entity,
+) -> PlacementVerdict {
+    if queue.is_reserved(piece) {
+        return PlacementVerdict::Denied("This spot is reserved");
+    }
+    evaluate(&rules.0, context)
+}
+
+// Shift-placing something unaffordable reserves the spot instead
+fn can_queue(rules: &PlacementRules, context: &PlacementContext) -> bool {
+    let unlimited = PlacementContext {
+        money: i32::MAX,
+        ..context.clone()
+    };
+    context.money < context.tower.price() && evaluate(&rules.0, &unlimited).is_allowed()
+}
+
+// Blowing direction picked for the fan being placed, None blows against the path
+#[derive(Resource, Debug, Default, Clone, Copy, Reflect)]
+pub(crate) struct FanAim(pub(crate) Option<CellDirection>);
+
+impl FanAim {
+    // A fan can't blow into the surface it is mounted on
+    fn resolve(
+        &self,
+        mount: CellDirection,
+        location: Vec3,
+        nodes: &Query<(&Transform, &PathNode)>,
+    ) -> Option<CellDirection> {
+        self.0
+            .filter(|w| *w != mount)
+            .or_else(|| against_path(location, nodes.iter()))
+    }
+}
 
 impl TowerPreview {
     fn reset(&mut self) {
@@ -139,6 +249,7 @@ fn on_exit_placement_state(
 fn tower_placement_change(
     mut tower_placement_events: EventReader<TowerPlacementEvent>,
     mut preview: ResMut<TowerPreview>,
+    mut fan_aim: ResMut<FanAim>,
     sprites: Option<Res<TowerSprites>>,
     spawned_previews: Query<(Entity, &GlobalTransform), With<SpawnedPreview>>,
     mut commands: Commands,
@@ -156,37 +267,102 @@ fn tower_placement_change(
         preview.reset();
     }
 
+    if preview.position_entity != Some(*parent) {
+        fan_aim.0 = None;
+    }
+
     *preview = TowerPreview {
         tower: Some(*tower),
         position_entity: Some(*parent),
         cell_direction: Some(*placement),
     };
 
+    let offset = placement.sprite_offset(&tower);
     commands.entity(*parent).with_children(|builder| {
         builder
             .spawn((
                 sprites.tower_bundle(tower, placement),
-                placement.sprite_offset(&tower),
+                offset,
                 SpawnedPreview,
                 Pickable::default(),
             ))
-            .observe(observe_placeholder);
+            .observe(observe_placeholder)
+            .with_child((
+                PreviewReason,
+                Text2d::default(),
+                TextColor(tailwind::RED_300.into()),
+                TextFont::from_font_size(18.0).with_font(LABEL_FONT),
+                // Undo the mirroring of right-facing towers so the text stays readable
+                Transform::from_xyz(0., 7., 1.).with_scale(Vec3::new(
+                    0.06 * offset.scale.x.signum(),
+                    0.06,
+                    1.,
+                )),
+                Pickable::IGNORE,
+            ));
     });
 }
 
-fn observe_placeholder(
-    trigger: Trigger<Pointer<Click>>,
-    spawned_previews: Query<(), With<SpawnedPreview>>,
-    mut commands: Commands,
-    mut next_pointer_state: ResMut<NextState<PointerInteractionState>>,
-    mut tower_placement_writer: EventWriter<TowerPlacementEvent>,
-    mut player_state: ResMut<PlayerState>,
-    input: Res<ButtonInput<KeyCode>>,
+fn show_placement_verdict(
     preview: Res<TowerPreview>,
-    relationships: Query<&Children>,
+    player_state: Res<PlayerState>,
+    rules: Res<PlacementRules>,
+    queue: Res<BuildQueue>,
     towers: Query<(&ChildOf, &Tower)>,
-    adjacent_placements: Query<(Entity, &Adjacent)>,
+    pieces: Query<(Entity, &Adjacent)>,
+    mut spawned_previews: Query<&mut Sprite, With<SpawnedPreview>>,
+    mut reasons: Query<&mut Text2d, With<PreviewReason>>,
+) {
+    let (Some(tower), Some(entity), Some(orientation)) = (
+        preview.tower,
+        preview.position_entity,
+        preview.cell_direction,
+    ) else {
+        return;
+    };
+    let Ok((_, piece)) = pieces.get(entity) else {
+        return;
+    };
+
+    let placed = placed_towers(&towers, &pieces);
+    let context = PlacementContext {
+        tower,
+        piece: *piece,
+        orientation,
+        money: player_state.money,
+        placed: &placed,
+    };
+    let verdict = placement_verdict(&rules, &queue, &context, entity);
+    let queueable = !queue.is_reserved(entity) && can_queue(&rules, &context);
+
+    let tint = if verdict.is_allowed() {
+        Color::srgba(0.6, 1.0, 0.6, 0.85)
+    } else if queueable {
+        Color::srgba(1.0, 0.85, 0.45, 0.85)
+    } else {
+        Color::srgba(1.0, 0.45, 0.45, 0.85)
+    };
+    for mut sprite in spawned_previews.iter_mut() {
+        if sprite.color != tint {
+            sprite.color = tint;
+        }
+    }
+    let reason = match verdict.reason() {
+        Some(reason) if queueable => format!("{reason}, shift to queue"),
+        reason => reason.unwrap_or_default().to_string(),
+    };
+    for mut text in reasons.iter_mut() {
+        if text.0 != reason {
+            text.0 = reason.clone();
+        }
+    }
+}
+
+fn observe_placeholder(
+    trigger: Trigger<Pointer<Click>>,
+    actions: Actions,
     hotbar: Query<(), With<HotbarItem>>,
+    mut placer: Placer,
     mut timer: Local<BodgeTimer>,
     time: Res<Time>,
 ) {
@@ -202,99 +378,93 @@ fn observe_placeholder(
         return;
     }
 
-    let total_previews = spawned_previews.iter().len();
-    if total_previews != 1 {
-        return;
-    }
-
-    let Some(tower) = preview.tower else {
-        return;
-    };
+    placer.place_preview(actions.pressed(Action::Modifier));
+}
 
-    let Some(entity) = preview.position_entity else {
-        return;
-    };
+// Everything needed to place the previewed tower, for clicks and the grid cursor alike
+#[derive(SystemParam)]
+pub(crate) struct Placer<'w, 's> {
+    commands: Commands<'w, 's>,
+    next_pointer_state: ResMut<'w, NextState<PointerInteractionState>>,
+    tower_placement_writer: EventWriter<'w, TowerPlacementEvent>,
+    player_state: ResMut<'w, PlayerState>,
+    preview: Res<'w, TowerPreview>,
+    rules: Res<'w, PlacementRules>,
+    queue: ResMut<'w, BuildQueue>,
+    sprites: Res<'w, TowerSprites>,
+    fan_aim: Res<'w, FanAim>,
+    spawned_previews: Query<'w, 's, (), With<SpawnedPreview>>,
+    towers: Query<'w, 's, (&'static ChildOf, &'static Tower)>,
+    adjacent_placements: Query<'w, 's, (Entity, &'static Adjacent)>,
+}
 
-    let Some(orientation) = preview.cell_direction else {
-        return;
-    };
+impl Placer<'_, '_> {
+    // With shift held the player keeps placing, and unaffordable spots are reserved
+    pub(crate) fn place_preview(&mut self, shift: bool) {
+        let total_previews = self.spawned_previews.iter().len();
+        if total_previews != 1 {
+            return;
+        }
 
-    // prevent placement if user cannot afford tower
-    if !player_state.can_afford(tower.price()) {
-        commands.trigger(DisplayFlashMessage::new("Insufficient funds"));
-        return;
-    }
+        let Some(tower) = self.preview.tower else {
+            return;
+        };
 
-    // prevent double placement
-    if let Ok(children) = relationships.get(entity) {
-        for child in children {
-            if let Ok(_) = towers.get(*child) {
-                commands.trigger(DisplayFlashMessage::new("There is already a tower here"));
-                return;
-            }
-        }
-    }
+        let Some(entity) = self.preview.position_entity else {
+            return;
+        };
 
-    if tower.requires_adjecent_wall() {
-        if let Ok((_, occupied_adjacent)) = adjacent_placements.get(entity) {
-            if tower.requires_floor_placement()
-                && occupied_adjacent.exact_position != ExactPosition::Floor
-            {
-                commands.trigger(DisplayFlashMessage::new(
-                    "This tower must be placed on a floor panel",
-                ));
-                return;
-            } else if tower.requires_floor_placement() && occupied_adjacent.id.unit_y == 0 {
-                commands.trigger(DisplayFlashMessage::new(
-                    "This tower cannot be placed on the bottom",
-                ));
-                return;
-            }
-            for (other_entity, target_adjacent) in adjacent_placements {
-                if occupied_adjacent.id == target_adjacent.id {
-                    if let Ok(children) = relationships.get(other_entity) {
-                        for child in children {
-                            if let Ok(_) = towers.get(*child) {
-                                commands.trigger(DisplayFlashMessage::new(
-                                    "This tower requires both sides of the wall",
-                                ));
-                                return;
-                            }
-                        }
-                    }
-                }
-            }
+        let Some(orientation) = self.preview.cell_direction else {
+            return;
         };
-    }
 
-    for (parent, tower) in towers {
-        if tower.requires_adjecent_wall() {
-            if let Ok((_, occupied_adjacent)) = adjacent_placements.get(parent.0) {
-                for (target_e, target_adjacent) in adjacent_placements {
-                    if target_e == entity && occupied_adjacent.id == target_adjacent.id {
-                        commands.trigger(DisplayFlashMessage::new(
-                            "Cannot place this tower under a trap door",
-                        ));
-                        return;
-                    }
-                }
-            }
+        let Ok((_, piece)) = self.adjacent_placements.get(entity) else {
+            return;
+        };
+        let placed = placed_towers(&self.towers, &self.adjacent_placements);
+        let context = PlacementContext {
+            tower,
+            piece: *piece,
+            orientation,
+            money: self.player_state.money,
+            placed: &placed,
+        };
+        let verdict = placement_verdict(&self.rules, &self.queue, &context, entity);
+        if shift && !self.queue.is_reserved(entity) && can_queue(&self.rules, &context) {
+            let snapshot = TowerSnapshot {
+                tower,
+                piece: entity,
+                orientation,
+                fan_direction: self.fan_aim.0,
+                priority: TargetPriority::default(),
+                preplaced: None,
+            };
+            queue_build(&mut self.commands, &mut self.queue, &self.sprites, snapshot);
+            self.commands.trigger(DisplayFlashMessage::new(format!(
+                "{} queued, it will be built once affordable",
+                tower.name()
+            )));
+            return;
+        }
+        if let Some(reason) = verdict.reason() {
+            self.commands.trigger(DisplayFlashMessage::new(reason));
+            return;
         }
-    }
 
-    player_state.money -= tower.price();
-    tower_placement_writer.write(TowerPlacementEvent::Accepted(tower, entity, orientation));
+        self.player_state.money -= tower.price();
+        self.tower_placement_writer
+            .write(TowerPlacementEvent::Accepted(tower, entity, orientation));
 
-    if !input.pressed(KeyCode::ShiftLeft) && !input.pressed(KeyCode::ShiftRight) {
-        next_pointer_state.set(PointerInteractionState::Selecting);
+        if !shift {
+            self.next_pointer_state
+                .set(PointerInteractionState::Selecting);
+        }
     }
 }
 
 fn on_turret_placement_hover(
     trigger: Trigger<Pointer<Over>>,
-    walls: Query<&Wall>,
-    floors: Query<(), With<Floor>>,
-    ceilings: Query<(), With<Ceiling>>,
+    pieces: Query<(Entity, &Adjacent)>,
     pointer_input_state: Res<State<PointerInteractionState>>,
     previews: Res<TowerPreview>,
     relationships: Query<&Children>,
@@ -317,37 +487,201 @@ fn on_turret_placement_hover(
         }
     }
 
-    if let Ok(wall) = walls.get(entity) {
-        let placement = match wall.0 {
-            WallDirection::Left => CellDirection::Left,
-            WallDirection::Right => CellDirection::Right,
-        };
-        tower_placement_writer.write(TowerPlacementEvent::Requested(tower, entity, placement));
-    } else if ceilings.get(entity).is_ok() {
-        tower_placement_writer.write(TowerPlacementEvent::Requested(
-            tower,
-            entity,
-            CellDirection::Up,
-        ));
-    } else if floors.get(entity).is_ok() {
-        tower_placement_writer.write(TowerPlacementEvent::Requested(
-            tower,
-            entity,
-            CellDirection::Down,
-        ));
+    let Ok((_, hovered)) = pieces.get(entity) else {
+        return;
+    };
+
+    // Prefer the hovered piece, otherwise the first facing the tower allows in that cell
+    let placement = hovered.exact_position.mount_direction();
+    let mount = if tower.orientations().contains(&placement) {
+        Some((entity, placement))
+    } else {
+        mounts_in_cell(tower, hovered.cell(), &pieces)
+            .into_iter()
+            .next()
+    };
+
+    if let Some((piece, placement)) = mount {
+        tower_placement_writer.write(TowerPlacementEvent::Requested(tower, piece, placement));
+    }
+}
+
+// Every piece a tower could be mounted on in a cell, in the tower's rotation order
+pub(crate) fn mounts_in_cell(
+    tower: Tower,
+    cell: IVec2,
+    pieces: &Query<(Entity, &Adjacent)>,
+) -> Vec<(Entity, CellDirection)> {
+    let mut mounts: Vec<_> = pieces
+        .iter()
+        .filter(|(_, adjacent)| adjacent.cell() == cell)
+        .filter_map(|(e, adjacent)| {
+            let direction = adjacent.exact_position.mount_direction();
+            tower
+                .orientations()
+                .iter()
+                .position(|w| *w == direction)
+                .map(|order| (order, e, direction))
+        })
+        .collect();
+    mounts.sort_by_key(|(order, _, _)| *order);
+    mounts.into_iter().map(|(_, e, d)| (e, d)).collect()
+}
+
+fn rotate_placement(
+    actions: Actions,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    pointer_input_state: Res<State<PointerInteractionState>>,
+    preview: Res<TowerPreview>,
+    pieces: Query<(Entity, &Adjacent)>,
+    mut tower_placement_writer: EventWriter<TowerPlacementEvent>,
+) {
+    let wheel: f32 = mouse_wheel_events
+        .read()
+        .filter(|w| w.unit == MouseScrollUnit::Line)
+        .map(|w| w.y)
+        .sum();
+
+    let PointerInteractionState::Placing(tower) = *pointer_input_state.get() else {
+        return;
+    };
+
+    let step: isize = if actions.just_pressed(Action::Rotate) || wheel < 0. {
+        1
+    } else if wheel > 0. {
+        -1
+    } else {
+        return;
+    };
+
+    let Some(current) = preview.position_entity else {
+        return;
+    };
+    let Ok((_, adjacent)) = pieces.get(current) else {
+        return;
+    };
+
+    let mounts = mounts_in_cell(tower, adjacent.cell(), &pieces);
+    let Some(index) = mounts.iter().position(|(e, _)| *e == current) else {
+        return;
+    };
+    if mounts.len() < 2 {
+        return;
+    }
+
+    let (piece, placement) =
+        mounts[(index as isize + step).rem_euclid(mounts.len() as isize) as usize];
+    tower_placement_writer.write(TowerPlacementEvent::Requested(tower, piece, placement));
+}
+
+fn rotate_fan_aim(
+    actions: Actions,
+    preview: Res<TowerPreview>,
+    spawned_previews: Query<&GlobalTransform, With<SpawnedPreview>>,
+    nodes: Query<(&Transform, &PathNode)>,
+    mut fan_aim: ResMut<FanAim>,
+    mut commands: Commands,
+) {
+    if !actions.just_pressed(Action::AimFan) || preview.tower != Some(Tower::Fan) {
+        return;
+    }
+    let (Some(mount), Ok(location)) = (preview.cell_direction, spawned_previews.single()) else {
+        return;
+    };
+
+    let current = fan_aim
+        .resolve(mount, location.translation(), &nodes)
+        .unwrap_or(mount);
+    let mut next = current.clockwise();
+    if next == mount {
+        next = next.clockwise();
+    }
+    fan_aim.0 = Some(next);
+    commands.trigger(DisplayFlashMessage::new(format!("Fan blowing {:?}", next)));
+}
+
+fn draw_fan_aim(
+    preview: Res<TowerPreview>,
+    spawned_previews: Query<&GlobalTransform, With<SpawnedPreview>>,
+    nodes: Query<(&Transform, &PathNode)>,
+    fan_aim: Res<FanAim>,
+    mut gizmos: Gizmos,
+) {
+    if preview.tower != Some(Tower::Fan) {
+        return;
     }
+    let (Some(mount), Ok(location)) = (preview.cell_direction, spawned_previews.single()) else {
+        return;
+    };
+    let Some(direction) = fan_aim.resolve(mount, location.translation(), &nodes) else {
+        return;
+    };
+
+    let start = location.translation().xy();
+    gizmos.arrow_2d(
+        start,
+        start + direction.vec() * LEVEL_SCALING * 0.8,
+        Color::WHITE.with_alpha(0.8),
+    );
+}
+
+pub(crate) fn spawn_tower(commands: &mut Commands, snapshot: TowerSnapshot) {
+    spawn_tower_with(commands, snapshot, ().store());
+}
+
+// Like `spawn_tower`, with extra components layered on top of the prefab
+pub(crate) fn spawn_tower_with(
+    commands: &mut Commands,
+    snapshot: TowerSnapshot,
+    extra: ComponentTree,
+) {
+    let TowerSnapshot {
+        tower,
+        piece,
+        orientation,
+        fan_direction,
+        priority,
+        preplaced,
+    } = snapshot;
+    let aim = match tower {
+        Tower::Fan => FanNeedsDirection(fan_direction.filter(|w| *w != orientation)).store(),
+        _ => ().store(),
+    };
+    let preplaced = match preplaced {
+        Some(preplaced) => preplaced.store(),
+        None => ().store(),
+    };
+    commands.entity(piece).with_children(|commands| {
+        commands.compose(
+            crate::prefabs::towers::tower(tower, orientation)
+                + orientation.sprite_offset(&tower).store()
+                + priority.store()
+                + aim
+                + preplaced
+                + extra,
+        );
+    });
 }
 
-fn place_towers(mut place_events: EventReader<TowerPlacementEvent>, mut commands: Commands) {
+fn place_towers(
+    mut place_events: EventReader<TowerPlacementEvent>,
+    fan_aim: Res<FanAim>,
+    mut history: ResMut<ActionHistory>,
+    mut commands: Commands,
+) {
     for event in place_events.read() {
         match *event {
             TowerPlacementEvent::Accepted(tower, entity, orientation) => {
-                commands.entity(entity).with_children(|commands| {
-                    commands.compose(
-                        crate::prefabs::towers::tower(tower, orientation)
-                            + orientation.sprite_offset(&tower).store(),
-                    );
-                });
+                let snapshot = TowerSnapshot {
+                    tower,
+                    piece: entity,
+                    orientation,
+                    fan_direction: fan_aim.0,
+                    priority: TargetPriority::default(),
+                    preplaced: None,
+                };
+                spawn_tower(&mut commands, snapshot);
+                history.record(TowerAction::Placed(snapshot, tower.price()));
                 info!("Placed {:?} at {:?}", tower, entity);
             }
             _ => {}
@@ -359,10 +693,7 @@ fn right_click_tower_options(
     triggers: Trigger<Pointer<Click>>,
     windows: Query<&Window>,
     cameras: Query<(&Camera, &GlobalTransform)>,
-    preview: Res<TowerPreview>,
-    mut player_state: ResMut<PlayerState>,
-    towers: Query<(Entity, &GlobalTransform, &Tower)>,
-    mut commands: Commands,
+    mut seller: TowerSeller,
 ) {
     if triggers.event().button == PointerButton::Secondary {
         let Ok(window) = windows.single() else {
@@ -385,24 +716,103 @@ fn right_click_tower_options(
 
         info!(window_position=?window_cursor_position, game_position=?game_cursor_position, "Cusor Position on click");
 
-        let mut in_range = towers
+        seller.sell_near(game_cursor_position, 5.0);
+    }
+}
+
+// Selling by right click or from the grid cursor
+#[derive(SystemParam)]
+pub(crate) struct TowerSeller<'w, 's> {
+    player_state: ResMut<'w, PlayerState>,
+    towers: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static Tower,
+            Option<&'static Preplaced>,
+        ),
+    >,
+    ghosts: Query<'w, 's, (Entity, &'static GlobalTransform), With<BuildGhost>>,
+    queue: ResMut<'w, BuildQueue>,
+    snapshots: Query<
+        'w,
+        's,
+        (
+            &'static ChildOf,
+            &'static CellDirection,
+            &'static TargetPriority,
+            Option<&'static FireDirection>,
+        ),
+    >,
+    history: ResMut<'w, ActionHistory>,
+    commands: Commands<'w, 's>,
+}
+
+impl TowerSeller<'_, '_> {
+    // Sells the closest tower within `radius`, or cancels a queued one there
+    pub(crate) fn sell_near(&mut self, position: Vec2, radius: f32) {
+        // Cancelling a reservation takes priority, it has nothing to sell
+        let ghost = self
+            .ghosts
             .iter()
-            .filter(|(_, transform, _)| {
-                transform.translation().xy().distance(game_cursor_position) < 5.0
+            .map(|(entity, transform)| {
+                let distance = transform.translation().xy().distance(position);
+                (entity, distance)
             })
+            .filter(|(_, distance)| *distance < radius)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((ghost, _)) = ghost {
+            if let Some(cancelled) = self.queue.cancel(&mut self.commands, ghost) {
+                self.commands.trigger(DisplayFlashMessage::new(format!(
+                    "Cancelled queued {}",
+                    cancelled.snapshot.tower.name()
+                )));
+            }
+            return;
+        }
+
+        let mut in_range = self
+            .towers
+            .iter()
+            .filter(|(_, transform, _, _)| transform.translation().xy().distance(position) < radius)
             .collect::<Vec<_>>();
 
         in_range.sort_by(|a, b| {
             a.1.translation()
                 .xy()
-                .distance(game_cursor_position)
-                .partial_cmp(&b.1.translation().xy().distance(game_cursor_position))
+                .distance(position)
+                .partial_cmp(&b.1.translation().xy().distance(position))
                 .unwrap()
         });
 
-        if let Some((entity, _, tower)) = in_range.into_iter().next() {
-            player_state.money += tower.price();
-            commands.entity(entity).despawn();
+        if let Some((entity, _, tower, preplaced)) = in_range.into_iter().next() {
+            if preplaced.is_some_and(|w| w.locked) {
+                self.commands
+                    .trigger(DisplayFlashMessage::new("This tower can't be sold"));
+                return;
+            }
+            let refund = if preplaced.is_some() {
+                0
+            } else {
+                tower.price()
+            };
+            if let Ok((ChildOf(piece), orientation, priority, fire_direction)) =
+                self.snapshots.get(entity)
+            {
+                let snapshot = TowerSnapshot {
+                    tower: *tower,
+                    piece: *piece,
+                    orientation: *orientation,
+                    fan_direction: fire_direction.map(|w| w.0),
+                    priority: *priority,
+                    preplaced: preplaced.copied(),
+                };
+                self.history.record(TowerAction::Sold(snapshot, refund));
+            }
+            self.player_state.money += refund;
+            self.commands.entity(entity).despawn();
         }
     }
 }
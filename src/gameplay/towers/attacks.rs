@@ -20,6 +20,8 @@ use super::{
     common::{TowerFired, TowerTriggerRange},
     directional::FireDirection,
     piston::Shove,
+    ranged::FireProjectile,
+    targeting::{TargetPriority, TargetStats},
 };
 use crate::{
     assets::{SoundEffects, sound_effects::FireSoundEffect},
@@ -30,6 +32,7 @@ use crate::{
             AttackData, AttackSpecification, DamageType, Droplet, LiquidType, Puddle,
             TowerAttackType,
         },
+        stats::{AttackDamage, AttackStrength, PushForce, Stat},
         status_effects::damage_multiplier,
     },
     demo::enemy_health::{EnemyHealth, TryDamageToEnemy},
@@ -62,6 +65,7 @@ pub fn do_tower_attacks(
     mut contact_events: EventWriter<AttackEnemiesInContact>,
     mut drop_events: EventWriter<DropLiquid>,
     mut detect_trap_door_events: EventWriter<DetectTrapDoor>,
+    mut projectile_events: EventWriter<FireProjectile>,
     towers: Query<(&Tower, &Children, &GlobalTransform)>,
     ranges: Query<(), With<TowerTriggerRange>>,
 ) {
@@ -89,6 +93,9 @@ pub fn do_tower_attacks(
             TowerAttackType::ModifiesSelf => {
                 detect_trap_door_events.write(DetectTrapDoor(event.0));
             }
+            TowerAttackType::Projectile(attack_effects) => {
+                projectile_events.write(FireProjectile(event.0, attack_effects));
+            }
         }
     }
 }
@@ -116,6 +123,8 @@ pub fn dispatch_attack_effects(
                     damage_type: *dmg_type,
                     enemy: *target,
                     strength: 1,
+                    source: Some(*source),
+                    status: None,
                 });
             }
             AttackData::Push {
@@ -127,6 +136,7 @@ pub fn dispatch_attack_effects(
                     *target,
                     *direction,
                     force * damage_multiplier(*strength),
+                    *source,
                 ));
             }
             AttackData::Status { status, strength } => {
@@ -134,6 +144,7 @@ pub fn dispatch_attack_effects(
                     status: *status,
                     enemy: *target,
                     strength: *strength,
+                    source: Some(*source),
                 });
             }
         }
@@ -166,7 +177,9 @@ pub fn attack_contact_enemies(
     directions: Query<&FireDirection>,
     parents: Query<&ChildOf, With<TowerTriggerRange>>,
     enemies: Query<(), With<EnemyHealth>>,
-    towers: Query<(&Tower, &CellDirection, &AnimationFrameQueue)>,
+    tower_stats: Query<(&Stat<AttackDamage>, &Stat<PushForce>, &Stat<AttackStrength>)>,
+    priorities: Query<&TargetPriority>,
+    target_stats: Query<TargetStats>,
 ) {
     for &AttackEnemiesInContact(sensor, ref effects) in events.read() {
         let direction = parents
@@ -176,32 +189,40 @@ pub fn attack_contact_enemies(
             .flatten()
             .unwrap_or(CellDirection::Up);
 
-        let enemies: Vec<_> = collisions
-            .entities_colliding_with(sensor)
-            .filter(|w| enemies.get(*w).is_ok())
-            .collect();
+        let (damage_mul, push_mul, strength) = parents
+            .get(sensor)
+            .ok()
+            .and_then(|w| tower_stats.get(w.0).ok())
+            .map(|(damage, push, strength)| {
+                (
+                    damage.current_value(),
+                    push.current_value(),
+                    strength.current_value().round().max(1.) as usize,
+                )
+            })
+            .unwrap_or((1., 1., 1));
+
+        let priority = parents
+            .get(sensor)
+            .ok()
+            .and_then(|w| priorities.get(w.0).ok())
+            .copied()
+            .unwrap_or_default();
+
+        let enemies = priority.select(
+            collisions
+                .entities_colliding_with(sensor)
+                .filter(|w| enemies.get(*w).is_ok())
+                .collect(),
+            &target_stats,
+        );
 
         for effect in effects {
             for enemy in &enemies {
                 attack_events.write(ApplyAttackData {
                     target: *enemy,
                     source: sensor,
-                    effect: match effect {
-                        AttackSpecification::Damage(damage_type, damage) => AttackData::Damage {
-                            dmg_type: *damage_type,
-                            strength: 1,
-                            damage: *damage,
-                        },
-                        AttackSpecification::Push(force) => AttackData::Push {
-                            direction: direction,
-                            strength: 1,
-                            force: *force,
-                        },
-                        AttackSpecification::Status(status_enum) => AttackData::Status {
-                            status: *status_enum,
-                            strength: 1,
-                        },
-                    },
+                    effect: effect.resolve(direction, strength, damage_mul, push_mul),
                 });
             }
         }
@@ -8,11 +8,19 @@ use bevy::{
         query::{Changed, With, Without},
         system::{Commands, Query, Res},
     },
+    math::Vec3,
     reflect::Reflect,
     time::{Time, Timer},
+    transform::components::Transform,
 };
 
-use crate::{data::Tower, demo::enemy_health::EnemyHealth};
+use crate::{
+    data::{
+        Tower,
+        stats::{FireRate, Stat, TriggerRange},
+    },
+    demo::enemy_health::EnemyHealth,
+};
 
 // Attached to sensor colliders that detect enemies for towers
 #[derive(Copy, Clone, Debug, Reflect, Component, PartialEq, Eq)]
@@ -82,11 +90,18 @@ pub fn remove_tower_targets(
 }
 
 pub fn towers_fire(
-    towers: Query<(Entity, &Tower), (With<TowerHasTargets>, Without<Cooldown>)>,
+    towers: Query<
+        (Entity, &Tower, Option<&Stat<FireRate>>),
+        (With<TowerHasTargets>, Without<Cooldown>),
+    >,
     mut fire_events: EventWriter<TowerFired>,
     mut commands: Commands,
 ) {
-    for (e, tower) in towers.iter() {
+    for (e, tower, fire_rate) in towers.iter() {
+        // Disabled towers (e.g. Frozen) hold their fire
+        if fire_rate.is_some_and(|w| w.current_value() <= 0.) {
+            continue;
+        }
         commands.entity(e).insert(Cooldown::new(tower.cooldown()));
         fire_events.write(TowerFired(e));
     }
@@ -103,9 +118,30 @@ pub fn remove_cooldown(
     }
 }
 
-pub fn tick_cooldown(mut cooldown_timers: Query<&mut Cooldown>, time: Res<Time>) {
-    for mut timer in cooldown_timers.iter_mut() {
-        timer.0.tick(time.delta());
+pub fn tick_cooldown(
+    mut cooldown_timers: Query<(&mut Cooldown, Option<&Stat<FireRate>>)>,
+    time: Res<Time>,
+) {
+    for (mut timer, fire_rate) in cooldown_timers.iter_mut() {
+        let rate = fire_rate.map(|w| w.current_value().max(0.)).unwrap_or(1.);
+        timer.0.tick(time.delta().mul_f32(rate));
+    }
+}
+
+pub fn scale_trigger_zones(
+    towers: Query<(&Stat<TriggerRange>, &Children), With<Tower>>,
+    mut zones: Query<&mut Transform, With<TowerTriggerRange>>,
+) {
+    for (range, children) in towers.iter() {
+        let scale = Vec3::splat(range.current_value().max(0.));
+        for child in children.iter() {
+            if let Ok(mut transform) = zones.get_mut(*child) {
+                // Avoid touching the collider unless the range actually changed
+                if transform.scale != scale {
+                    transform.scale = scale;
+                }
+            }
+        }
     }
 }
 
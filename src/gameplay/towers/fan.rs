@@ -1,59 +1,112 @@
-use avian2d::{
-    parry::query::RayCast,
-    prelude::{Collider, Collisions, ExternalImpulse, LayerMask, RayCaster, RayHits, Sensor},
+use avian2d::prelude::{
+    Collider, Collisions, ExternalImpulse, LayerMask, RayCaster, RayHits, Sensor,
 };
 use bevy::{
+    color::Color,
     ecs::{
         component::Component,
         entity::{Entity, EntityHashSet},
+        hierarchy::ChildOf,
         query::With,
         system::{Commands, Query, Res},
     },
     math::{Dir2, Vec2, Vec3},
     prelude::Vec3Swizzles,
     reflect::Reflect,
-    time::Time,
+    sprite::Sprite,
+    time::{Time, Timer, TimerMode},
     transform::components::{GlobalTransform, Transform},
 };
 
 use crate::{
+    data::{
+        Tower,
+        projectiles::{AttackSpecification, TowerAttackType},
+        stats::{PushForce, Stat},
+        status_effects::{Slippery, StatusEffect},
+    },
     demo::enemy_health::EnemyHealth,
     level::{components::PathNode, resource::CellDirection},
     prefabs::physics::GamePhysicsLayer,
 };
 
-use super::directional::FireDirection;
+use super::{directional::FireDirection, piston::push_multiplier};
+
+pub const WIND_SPEED: f32 = 40.;
+pub const BLOWN_SECONDS: f32 = 0.5;
 
 #[derive(Component, Reflect, Debug, Clone, Copy)]
-pub struct ForceField(pub CellDirection);
+pub struct ForceField {
+    pub direction: CellDirection,
+    pub force: f32,
+}
 
+// Fans resolve their wind once placed, blowing the chosen way or against the path
 #[derive(Component, Reflect, Debug, Clone, Copy)]
-pub struct FanNeedsDirection;
+pub struct FanNeedsDirection(pub Option<CellDirection>);
 
 #[derive(Component, Reflect, Debug, Copy, Clone)]
 pub struct FanCaster(Entity);
 
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+pub struct WindParticle {
+    pub offset: f32,
+    pub lateral: f32,
+    pub length: f32,
+}
+
+// Enemies caught in a fan's wind, for a short while after leaving it
+#[derive(Component, Reflect, Debug, Clone)]
+pub struct Blown(pub Timer);
+
+impl Blown {
+    pub fn new() -> Self {
+        Self(Timer::from_seconds(BLOWN_SECONDS, TimerMode::Once))
+    }
+}
+
+// Blow against the direction enemies arrive at the nearest path node from
+pub fn against_path<'a>(
+    location: Vec3,
+    nodes: impl Iterator<Item = (&'a Transform, &'a PathNode)>,
+) -> Option<CellDirection> {
+    nodes
+        .map(|(transform, node)| {
+            (
+                location.distance(transform.translation.xy().extend(0.)),
+                node.prev_direction,
+            )
+        })
+        .min_by(|w, other| w.0.total_cmp(&other.0))
+        .map(|(_, direction)| direction.flip())
+}
+
+fn fan_force() -> f32 {
+    match Tower::Fan.attack_def() {
+        TowerAttackType::EntireCell(effects) => effects
+            .iter()
+            .find_map(|w| match w {
+                AttackSpecification::Push(force) => Some(*force),
+                _ => None,
+            })
+            .unwrap_or(0.),
+        _ => 0.,
+    }
+}
+
 pub fn spawn_fancasters(
-    fans: Query<(Entity, &GlobalTransform), With<FanNeedsDirection>>,
+    fans: Query<(Entity, &GlobalTransform, &FanNeedsDirection)>,
     nodes: Query<(&Transform, &PathNode)>,
     mut commands: Commands,
 ) {
     let filter: LayerMask = [GamePhysicsLayer::Level].into();
 
-    for (e, transform) in fans.iter() {
+    for (e, transform, FanNeedsDirection(chosen)) in fans.iter() {
         let (_, rotation, location) = transform.to_scale_rotation_translation();
 
-        let mut nodes_sorted_by_distance = nodes
-            .iter()
-            .map(|w| {
-                (
-                    location.distance(w.0.translation.xy().extend(0.)),
-                    (w.0, w.1.prev_direction),
-                )
-            })
-            .collect::<Vec<_>>();
-        nodes_sorted_by_distance.sort_by(|w, other| w.0.total_cmp(&other.0));
-        let direction = nodes_sorted_by_distance[0].1.1.flip();
+        let Some(direction) = chosen.or_else(|| against_path(location, nodes.iter())) else {
+            continue;
+        };
 
         let raycaster = RayCaster::new(Vec2::ZERO, Dir2::new_unchecked(direction.into()))
             .with_query_filter(avian2d::prelude::SpatialQueryFilter {
@@ -96,36 +149,99 @@ pub fn resolve_fancasters(
             };
             commands.entity(*fan_entity).with_children(|w| {
                 w.spawn((
-                    ForceField(direction),
+                    ForceField {
+                        direction,
+                        force: fan_force(),
+                    },
                     collider,
                     Sensor,
                     Transform::from_translation(translation),
-                ));
+                ))
+                .with_children(|field| {
+                    // Roughly one streak every few units, staggered across the width
+                    let count = (collider_size / 4.).ceil() as usize;
+                    for i in 0..count {
+                        field.spawn((
+                            WindParticle {
+                                offset: i as f32 * collider_size / count as f32,
+                                lateral: ((i * 7) % 5) as f32 * 1.6 - 3.2,
+                                length: collider_size,
+                            },
+                            wind_sprite(direction),
+                            Transform::from_xyz(0., 0., 1.),
+                        ));
+                    }
+                });
             });
-            println!("Distance: {}", distance);
             commands.entity(dropper).despawn();
         }
     }
 }
 
+fn wind_sprite(direction: CellDirection) -> Sprite {
+    let size = match direction {
+        CellDirection::Up | CellDirection::Down => Vec2::new(0.4, 2.5),
+        CellDirection::Left | CellDirection::Right => Vec2::new(2.5, 0.4),
+    };
+    Sprite::from_color(Color::WHITE.with_alpha(0.35), size)
+}
+
+pub fn animate_wind(
+    mut particles: Query<(&mut Transform, &mut WindParticle, &ChildOf)>,
+    fields: Query<(&ForceField, &ChildOf)>,
+    fans: Query<&Stat<PushForce>>,
+    time: Res<Time>,
+) {
+    let delta = time.delta().as_secs_f32();
+    for (mut transform, mut particle, ChildOf(field)) in particles.iter_mut() {
+        let Ok((ForceField { direction, .. }, ChildOf(fan))) = fields.get(*field) else {
+            continue;
+        };
+        let speed = WIND_SPEED * fans.get(*fan).map_or(1., |w| w.current_value());
+        particle.offset = (particle.offset + speed * delta).rem_euclid(particle.length);
+
+        let along = direction.vec();
+        let across = along.perp();
+        let position = along * (particle.offset - particle.length / 2.) + across * particle.lateral;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
 pub fn do_forcefields(
     collisions: Collisions,
-    fields: Query<(Entity, &ForceField)>,
-    mut enemies: Query<&mut ExternalImpulse, With<EnemyHealth>>,
+    fields: Query<(Entity, &ForceField, &ChildOf)>,
+    fans: Query<&Stat<PushForce>>,
+    mut enemies: Query<(&mut ExternalImpulse, Option<&StatusEffect<Slippery>>), With<EnemyHealth>>,
     time: Res<Time>,
+    mut commands: Commands,
 ) {
     let delta = time.delta().as_secs_f32();
-    for (field_e, ForceField(direction)) in fields.iter() {
+    for (field_e, ForceField { direction, force }, ChildOf(fan)) in fields.iter() {
         let direction: Vec2 = (*direction).into();
+        let force = force * fans.get(*fan).map_or(1., |w| w.current_value());
         let pushed_enemies: Vec<_> = collisions
             .entities_colliding_with(field_e)
             .filter(|w| enemies.get(*w).is_ok())
             .collect();
 
-        for enemy in pushed_enemies {
-            if let Ok(mut enemy) = enemies.get_mut(enemy) {
-                **enemy += direction * 45. * delta;
+        for enemy_e in pushed_enemies {
+            if let Ok((mut enemy, slippery)) = enemies.get_mut(enemy_e) {
+                **enemy += direction * force * delta * push_multiplier(slippery);
+                commands.entity(enemy_e).insert(Blown::new());
             }
         }
     }
 }
+
+pub fn expire_blown(
+    mut blown: Query<(Entity, &mut Blown)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (e, mut blown) in blown.iter_mut() {
+        blown.0.tick(time.delta());
+        if blown.0.finished() {
+            commands.entity(e).remove::<Blown>();
+        }
+    }
+}
@@ -14,13 +14,14 @@ use bevy::{
     time::Time,
     transform::components::{GlobalTransform, Transform},
 };
-use bevy_composable::app_impl::ComplexSpawnable;
+use bevy_composable::app_impl::{ComplexSpawnable, ComponentTreeable};
 
 use super::attacks::{ApplyAttackData, DropLiquid};
 use crate::{
     data::{
         Tower,
-        projectiles::{AttackData, AttackSpecification, DamageType, Droplet, Puddle},
+        projectiles::{DamageType, Droplet, LiquidPotency, Puddle},
+        stats::{AttackDamage, AttackStrength, Stat},
     },
     demo::enemy_health::{EnemyHealth, TryDamageToEnemy},
     gameplay::{animation::AnimationFrameQueue, shared_systems::Lifetime},
@@ -39,16 +40,22 @@ pub fn drop_liquids(
         &GlobalTransform,
         &CellDirection,
         &mut AnimationFrameQueue,
+        (&Stat<AttackDamage>, &Stat<AttackStrength>),
     )>,
 ) {
     for DropLiquid(e, liquid) in events.read() {
-        let Ok((tower, global_transform, cell_direction, mut animation)) = towers.get_mut(*e)
+        let Ok((tower, global_transform, cell_direction, mut animation, (damage, strength))) =
+            towers.get_mut(*e)
         else {
             warn!("Tower not found in dispatch_attack_effects");
             return;
         };
+        let potency = LiquidPotency {
+            strength: strength.current_value().round().max(1.) as usize,
+            damage_mul: damage.current_value(),
+        };
         let loc = global_transform.to_scale_rotation_translation().2.xy();
-        commands.compose(droplet(*liquid) + pos(loc.x, loc.y));
+        commands.compose(droplet(*liquid) + pos(loc.x, loc.y) + potency.store());
         animation.set_override(cell_direction.attack_frames(&tower));
     }
 }
@@ -56,7 +63,7 @@ pub fn drop_liquids(
 pub fn splat_droplets(
     trigger: Trigger<OnCollisionStart>,
     sensors: Query<(), With<Sensor>>,
-    droplets: Query<(&Transform, &Droplet)>,
+    droplets: Query<(&Transform, &Droplet, Option<&LiquidPotency>)>,
     mut commands: Commands,
 ) {
     let droplet = trigger.target();
@@ -64,10 +71,11 @@ pub fn splat_droplets(
 
     // We don't want droplets to do things when they hit sensors
     if sensors.get(other).is_err() {
-        if let Ok((transform, Droplet(liquid))) = droplets.get(droplet) {
+        if let Ok((transform, Droplet(liquid), potency)) = droplets.get(droplet) {
             let loc = transform.translation;
+            let potency = potency.copied().unwrap_or_default();
             commands.entity(droplet).despawn();
-            commands.compose(puddle(*liquid) + pos(loc.x, loc.y));
+            commands.compose(puddle(*liquid) + pos(loc.x, loc.y) + potency.store());
         }
     }
 }
@@ -75,30 +83,25 @@ pub fn splat_droplets(
 pub fn puddle_attacks(
     trigger: Trigger<OnCollisionStart>,
     enemies: Query<(), With<EnemyHealth>>,
-    puddles: Query<&Puddle>,
+    puddles: Query<(&Puddle, Option<&LiquidPotency>)>,
     mut attack_events: EventWriter<ApplyAttackData>,
 ) {
     let puddle = trigger.target();
     let other = trigger.collider;
 
     if enemies.get(other).is_ok() {
-        if let Ok(Puddle(liquid)) = puddles.get(puddle) {
+        if let Ok((Puddle(liquid), potency)) = puddles.get(puddle) {
+            let potency = potency.copied().unwrap_or_default();
             for effect in liquid.contact_effects() {
                 attack_events.write(ApplyAttackData {
                     target: other,
                     source: puddle,
-                    effect: match effect {
-                        AttackSpecification::Damage(damage_type, damage) => AttackData::Damage {
-                            dmg_type: damage_type,
-                            strength: 1,
-                            damage,
-                        },
-                        AttackSpecification::Push(_) => todo!(),
-                        AttackSpecification::Status(status_enum) => AttackData::Status {
-                            status: status_enum,
-                            strength: 1,
-                        },
-                    },
+                    effect: effect.resolve(
+                        CellDirection::Down,
+                        potency.strength,
+                        potency.damage_mul,
+                        1.,
+                    ),
                 });
             }
         }
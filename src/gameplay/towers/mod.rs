@@ -6,7 +6,7 @@ use super::{stats::StatSet, status_effects::common::status_debuff_multiplier};
 use crate::{
     PausableSystems,
     data::{
-        projectiles::{Droplet, Puddle},
+        projectiles::{Droplet, Projectile, Puddle},
         stats::MoveSpeed,
         status_effects::Frozen,
     },
@@ -18,10 +18,14 @@ use attacks::{
     attack_contact_enemies, dispatch_attack_effects, do_tower_attacks, play_tower_sfx,
 };
 use common::*;
-use fan::{ForceField, do_forcefields, resolve_fancasters, spawn_fancasters};
+use fan::{
+    Blown, FanNeedsDirection, ForceField, WindParticle, animate_wind, do_forcefields, expire_blown,
+    resolve_fancasters, spawn_fancasters,
+};
 use gravity_bullshit::{RangeDropper, drop_ranges, spawn_rangedroppers};
 use liquids::{drop_liquids, puddle_attacks, splat_droplets, stop_dropping_puddles};
 use piston::{Shove, do_shoves};
+use ranged::{FireProjectile, aim_ranged_towers, fire_projectiles, projectile_hits};
 use trap_door::{DetectTrapDoor, OpenTrapDoor, close_trap_door, detect_trap_door, open_trap_door};
 
 pub mod attacks;
@@ -31,6 +35,8 @@ pub mod fan;
 pub mod gravity_bullshit;
 pub mod liquids;
 pub mod piston;
+pub mod ranged;
+pub mod targeting;
 pub mod trap_door;
 
 pub(super) fn plugin(app: &mut App) {
@@ -40,12 +46,18 @@ pub(super) fn plugin(app: &mut App) {
         .register_type::<TowerFired>()
         .register_type::<FireDirection>()
         .register_type::<ForceField>()
+        .register_type::<FanNeedsDirection>()
+        .register_type::<WindParticle>()
+        .register_type::<Blown>()
         .register_type::<TowerHasTargets>()
+        .register_type::<targeting::TargetPriority>()
+        .register_type::<Projectile>()
         .register_type::<RangeDropper>();
 
     app.add_event::<DropLiquid>()
         .add_event::<TowerFired>()
         .add_event::<Shove>()
+        .add_event::<FireProjectile>()
         .add_event::<ApplyAttackData>()
         .add_event::<AttackEnemiesInContact>()
         .add_event::<ApplyAttackData>()
@@ -61,17 +73,23 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(add_observer_to_component::<Droplet, _, _, _, _>(
         splat_droplets,
     ));
+    app.add_observer(add_observer_to_component::<Projectile, _, _, _, _>(
+        projectile_hits,
+    ));
 
     app.add_systems(
         Update,
         (
             (tick_cooldown, remove_cooldown).chain(),
+            scale_trigger_zones,
+            (animate_wind, expire_blown),
             (
                 towers_fire,
                 do_tower_attacks,
                 (
                     attack_contact_enemies,
                     drop_liquids,
+                    fire_projectiles,
                     detect_trap_door,
                     open_trap_door,
                     close_trap_door,
@@ -86,6 +104,7 @@ pub(super) fn plugin(app: &mut App) {
             )
                 .chain(),
         )
+            .in_set(StatSet::Use)
             .in_set(PausableSystems)
             .run_if(in_state(Screen::Gameplay)),
     );
@@ -97,6 +116,7 @@ pub(super) fn plugin(app: &mut App) {
             remove_tower_targets,
             spawn_rangedroppers,
             spawn_fancasters,
+            aim_ranged_towers,
             drop_ranges,
             resolve_fancasters,
         )
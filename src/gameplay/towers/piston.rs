@@ -3,8 +3,8 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
-        event::{Event, EventReader},
-        query::With,
+        event::{Event, EventReader, EventWriter},
+        query::{Has, With},
         system::{Commands, Query},
     },
     math::Vec2,
@@ -13,23 +13,53 @@ use bevy::{
 };
 
 use crate::{
-    data::status_effects::damage_multiplier,
+    data::status_effects::{Reaction, Slippery, StatusEffect, damage_multiplier},
     demo::enemy_health::EnemyHealth,
+    gameplay::status_effects::common::ReactionTriggered,
     level::{components::PathNode, resource::CellDirection},
 };
 
-use super::directional::FireDirection;
+use super::{directional::FireDirection, fan::Blown};
 
+// Shoving an enemy that is already riding a fan's wind sends it much further
+pub const BLOWN_SHOVE_MULTIPLIER: f32 = 2.;
+
+// Enemy, direction, force and the tower doing the shoving
 #[derive(Event, Reflect, Clone, Debug, Copy, PartialEq)]
-pub struct Shove(pub Entity, pub CellDirection, pub f32);
+pub struct Shove(pub Entity, pub CellDirection, pub f32, pub Entity);
 
 pub fn do_shoves(
     mut events: EventReader<Shove>,
-    mut enemies: Query<&mut ExternalImpulse, With<EnemyHealth>>,
+    mut enemies: Query<
+        (
+            &mut ExternalImpulse,
+            Option<&StatusEffect<Slippery>>,
+            Has<Blown>,
+        ),
+        With<EnemyHealth>,
+    >,
+    mut reactions: EventWriter<ReactionTriggered>,
+    mut commands: Commands,
 ) {
-    for Shove(e, direction, power) in events.read() {
-        if let Ok(mut impulse) = enemies.get_mut(*e) {
-            **impulse += Into::<Vec2>::into(*direction) * power
+    for Shove(e, direction, power, source) in events.read() {
+        if let Ok((mut impulse, slippery, blown)) = enemies.get_mut(*e) {
+            let combo = if blown {
+                reactions.write(ReactionTriggered {
+                    reaction: Reaction::Slam,
+                    enemy: *e,
+                    source: Some(*source),
+                });
+                commands.entity(*e).remove::<Blown>();
+                BLOWN_SHOVE_MULTIPLIER
+            } else {
+                1.
+            };
+            **impulse += Into::<Vec2>::into(*direction) * power * push_multiplier(slippery) * combo
         }
     }
 }
+
+// Slippery enemies have nothing to brace against, so pushes carry them further
+pub fn push_multiplier(slippery: Option<&StatusEffect<Slippery>>) -> f32 {
+    slippery.map_or(1., |status| 1. + 0.5 * damage_multiplier(status.strength))
+}
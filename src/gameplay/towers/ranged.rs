@@ -0,0 +1,166 @@
+use avian2d::prelude::{OnCollisionStart, SpatialQuery, SpatialQueryFilter};
+use bevy::{
+    color::Color,
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        hierarchy::ChildOf,
+        observer::Trigger,
+        query::{Has, With},
+        system::{Commands, Query},
+    },
+    math::{Dir2, Vec3Swizzles},
+    prelude::warn,
+    reflect::Reflect,
+    transform::components::GlobalTransform,
+};
+use bevy_composable::app_impl::ComplexSpawnable;
+
+use super::{attacks::ApplyAttackData, common::TowerHasTargets, directional::FireDirection};
+use crate::{
+    data::{
+        Tower,
+        projectiles::{AttackSpecification, Projectile},
+        stats::{AttackDamage, AttackStrength, PushForce, Stat, TriggerRange},
+    },
+    demo::enemy_health::EnemyHealth,
+    level::components::{Architecture, LEVEL_SCALING, pos},
+    prefabs::{attacks::projectile, physics::GamePhysicsLayer as GPL},
+};
+
+pub const PROJECTILE_SPEED: f32 = 90.;
+
+#[derive(Event, Reflect, Debug, PartialEq, Clone)]
+pub struct FireProjectile(pub Entity, pub Vec<AttackSpecification>);
+
+fn max_distance(tower: &Tower, range: &Stat<TriggerRange>) -> f32 {
+    tower.range().unwrap_or(0.) * LEVEL_SCALING * range.current_value().max(0.)
+}
+
+// Ranged towers have no trigger zone, instead they look down their line of fire.
+// Level geometry is part of the mask so walls block line of sight.
+pub fn aim_ranged_towers(
+    towers: Query<(
+        Entity,
+        &Tower,
+        &GlobalTransform,
+        &FireDirection,
+        &ChildOf,
+        &Stat<TriggerRange>,
+        Has<TowerHasTargets>,
+    )>,
+    enemies: Query<(), With<EnemyHealth>>,
+    spatial_query: SpatialQuery,
+    mut commands: Commands,
+) {
+    for (e, tower, transform, FireDirection(direction), ChildOf(mount), range, has_targets) in
+        towers.iter()
+    {
+        if tower.range().is_none() {
+            continue;
+        }
+
+        let filter = SpatialQueryFilter::from_mask([GPL::Enemy, GPL::Level])
+            .with_excluded_entities([*mount]);
+        let sees_enemy = spatial_query
+            .cast_ray(
+                transform.translation().xy(),
+                Dir2::new_unchecked((*direction).into()),
+                max_distance(tower, range),
+                true,
+                &filter,
+            )
+            .is_some_and(|hit| enemies.contains(hit.entity));
+
+        if sees_enemy && !has_targets {
+            commands.entity(e).insert(TowerHasTargets);
+        } else if !sees_enemy && has_targets {
+            commands.entity(e).remove::<TowerHasTargets>();
+        }
+    }
+}
+
+pub fn fire_projectiles(
+    mut events: EventReader<FireProjectile>,
+    towers: Query<(
+        &Tower,
+        &GlobalTransform,
+        &FireDirection,
+        &ChildOf,
+        &Stat<TriggerRange>,
+        (&Stat<AttackDamage>, &Stat<PushForce>, &Stat<AttackStrength>),
+    )>,
+    mut commands: Commands,
+) {
+    for FireProjectile(e, effects) in events.read() {
+        let Ok((tower, transform, FireDirection(direction), ChildOf(mount), range, stats)) =
+            towers.get(*e)
+        else {
+            warn!("Tower not found in fire_projectiles");
+            continue;
+        };
+        let (damage, push, strength) = stats;
+        let strength = strength.current_value().round().max(1.) as usize;
+
+        let color = effects
+            .iter()
+            .find_map(|w| match w {
+                AttackSpecification::Damage(damage_type, _) => Some(damage_type.color()),
+                _ => None,
+            })
+            .unwrap_or(Color::WHITE);
+
+        let loc = transform.translation().xy();
+        let velocity = direction.vec() * PROJECTILE_SPEED;
+        commands.compose(
+            projectile(
+                Projectile {
+                    tower: *e,
+                    mount: *mount,
+                    effects: effects
+                        .iter()
+                        .map(|w| {
+                            w.resolve(
+                                *direction,
+                                strength,
+                                damage.current_value(),
+                                push.current_value(),
+                            )
+                        })
+                        .collect(),
+                },
+                velocity,
+                color,
+                max_distance(tower, range) / PROJECTILE_SPEED,
+            ) + pos(loc.x, loc.y),
+        );
+    }
+}
+
+pub fn projectile_hits(
+    trigger: Trigger<OnCollisionStart>,
+    projectiles: Query<&Projectile>,
+    enemies: Query<(), With<EnemyHealth>>,
+    level_parts: Query<(), With<Architecture>>,
+    mut attack_events: EventWriter<ApplyAttackData>,
+    mut commands: Commands,
+) {
+    let shot = trigger.target();
+    let other = trigger.collider;
+    let Ok(projectile) = projectiles.get(shot) else {
+        return;
+    };
+
+    if enemies.contains(other) {
+        for effect in &projectile.effects {
+            attack_events.write(ApplyAttackData {
+                target: other,
+                source: projectile.tower,
+                effect: effect.clone(),
+            });
+        }
+        commands.entity(shot).try_despawn();
+    } else if level_parts.contains(other) && other != projectile.mount {
+        commands.entity(shot).try_despawn();
+    }
+}
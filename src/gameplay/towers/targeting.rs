@@ -0,0 +1,159 @@
+use avian2d::prelude::LinearVelocity;
+use bevy::{
+    ecs::{component::Component, entity::Entity, query::QueryData, system::Query},
+    reflect::Reflect,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::demo::{enemy_health::EnemyHealth, enemy_movement::PathProgress};
+
+// Which enemy in range a tower hits, `All` keeps the cell-wide behavior
+#[derive(
+    Component, Copy, Clone, Debug, Default, Reflect, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum TargetPriority {
+    #[default]
+    All,
+    First,
+    Last,
+    Strongest,
+    Weakest,
+    Fastest,
+}
+
+impl TargetPriority {
+    const ORDER: [TargetPriority; 6] = [
+        TargetPriority::All,
+        TargetPriority::First,
+        TargetPriority::Last,
+        TargetPriority::Strongest,
+        TargetPriority::Weakest,
+        TargetPriority::Fastest,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetPriority::All => "All",
+            TargetPriority::First => "First",
+            TargetPriority::Last => "Last",
+            TargetPriority::Strongest => "Strongest",
+            TargetPriority::Weakest => "Weakest",
+            TargetPriority::Fastest => "Fastest",
+        }
+    }
+
+    pub fn next(&self) -> TargetPriority {
+        let i = Self::ORDER.iter().position(|w| w == self).unwrap();
+        Self::ORDER[(i + 1) % Self::ORDER.len()]
+    }
+
+    pub fn prev(&self) -> TargetPriority {
+        let i = Self::ORDER.iter().position(|w| w == self).unwrap();
+        Self::ORDER[(i + Self::ORDER.len() - 1) % Self::ORDER.len()]
+    }
+
+    // Narrows the enemies in range down to the ones this priority wants to hit
+    pub fn select(&self, enemies: Vec<Entity>, stats: &Query<TargetStats>) -> Vec<Entity> {
+        let key = |e: &Entity| -> f32 {
+            let Ok(stat) = stats.get(*e) else {
+                return f32::MIN;
+            };
+            match self {
+                TargetPriority::All => 0.,
+                TargetPriority::First => stat.progress.0,
+                TargetPriority::Last => -stat.progress.0,
+                TargetPriority::Strongest => stat.health.current() as f32,
+                TargetPriority::Weakest => -stat.health.current() as f32,
+                TargetPriority::Fastest => stat.velocity.length(),
+            }
+        };
+
+        if *self == TargetPriority::All {
+            return enemies;
+        }
+        enemies
+            .into_iter()
+            .max_by(|a, b| key(a).total_cmp(&key(b)))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[derive(QueryData)]
+pub struct TargetStats {
+    health: &'static EnemyHealth,
+    progress: &'static PathProgress,
+    velocity: &'static LinearVelocity,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, math::Vec2, prelude::World};
+
+    use super::*;
+
+    // Enemies as (health, path progress, speed)
+    fn select(
+        priority: TargetPriority,
+        enemies: &[(isize, f32, f32)],
+    ) -> (Vec<Entity>, Vec<Entity>) {
+        let mut world = World::new();
+        let spawned: Vec<_> = enemies
+            .iter()
+            .map(|(health, progress, speed)| {
+                world
+                    .spawn((
+                        EnemyHealth::new(*health),
+                        PathProgress(*progress),
+                        LinearVelocity(Vec2::new(*speed, 0.)),
+                    ))
+                    .id()
+            })
+            .collect();
+        let candidates = spawned.clone();
+        let selected = world
+            .run_system_once(move |stats: Query<TargetStats>| {
+                priority.select(candidates.clone(), &stats)
+            })
+            .unwrap();
+        (spawned, selected)
+    }
+
+    const ENEMIES: [(isize, f32, f32); 3] = [(10, 2., 30.), (30, 5., 10.), (20, 1., 50.)];
+
+    #[test]
+    fn all_keeps_every_enemy() {
+        let (spawned, selected) = select(TargetPriority::All, &ENEMIES);
+        assert_eq!(selected, spawned);
+    }
+
+    #[test]
+    fn picks_a_single_enemy_by_priority() {
+        for (priority, expected) in [
+            (TargetPriority::First, 1),
+            (TargetPriority::Last, 2),
+            (TargetPriority::Strongest, 1),
+            (TargetPriority::Weakest, 0),
+            (TargetPriority::Fastest, 2),
+        ] {
+            let (spawned, selected) = select(priority, &ENEMIES);
+            assert_eq!(selected, vec![spawned[expected]], "{}", priority.name());
+        }
+    }
+
+    #[test]
+    fn no_enemies_selects_nothing() {
+        let (_, selected) = select(TargetPriority::First, &[]);
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn cycling_visits_every_priority() {
+        let mut priority = TargetPriority::default();
+        for _ in 0..TargetPriority::ORDER.len() {
+            assert_eq!(priority.next().prev(), priority);
+            priority = priority.next();
+        }
+        assert_eq!(priority, TargetPriority::default());
+    }
+}
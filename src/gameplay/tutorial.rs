@@ -0,0 +1,264 @@
+use bevy::{color::palettes::tailwind, ecs::spawn::SpawnWith, prelude::*};
+
+use crate::{
+    data::{
+        Tower,
+        levels::LevelData,
+        tutorials::{TutorialGoal, TutorialStep, TutorialTarget, TutorialUi},
+    },
+    gameplay::{
+        economy::WaveCalled, level_setup::Preplaced, status_effects::common::ReactionTriggered,
+    },
+    level::{components::LEVEL_SCALING, resource::LevelSelect},
+    prelude::*,
+    save_data::SaveData,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TutorialAnchor>();
+    app.add_systems(OnEnter(Screen::Gameplay), start_tutorial);
+    app.add_systems(OnExit(Screen::Gameplay), stop_tutorial);
+    app.add_systems(
+        Update,
+        (
+            advance_tutorial.run_if(resource_exists::<ActiveTutorial>),
+            clear_tutorial_step.run_if(
+                resource_exists_and_changed::<ActiveTutorial>
+                    .or(resource_removed::<ActiveTutorial>),
+            ),
+            show_tutorial_step.run_if(resource_exists_and_changed::<ActiveTutorial>),
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_observer(complete_wave_step);
+}
+
+// Marks UI that tutorial steps can point at
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct TutorialAnchor(pub TutorialUi);
+
+#[derive(Resource, Debug, Clone)]
+struct ActiveTutorial {
+    level: usize,
+    steps: Vec<TutorialStep>,
+    current: usize,
+}
+
+impl ActiveTutorial {
+    fn step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.current)
+    }
+
+    fn goal(&self) -> Option<TutorialGoal> {
+        self.step().map(|w| w.goal)
+    }
+}
+
+#[derive(Component)]
+struct TutorialBubble;
+
+// The world cell marker of the current step
+#[derive(Component)]
+struct TutorialHighlight;
+
+// UI that was given an outline by the current step
+#[derive(Component)]
+struct TutorialOutlined;
+
+fn start_tutorial(
+    level_data: Res<LevelData>,
+    level_select: Res<LevelSelect>,
+    save_data: Res<SaveData>,
+    mut commands: Commands,
+) {
+    let level = level_select.0;
+    let steps = level_data.tutorial(level);
+    if steps.is_empty() || save_data.tutorials.completed_levels.contains(&level) {
+        return;
+    }
+    commands.insert_resource(ActiveTutorial {
+        level,
+        steps: steps.to_vec(),
+        current: 0,
+    });
+}
+
+fn stop_tutorial(mut commands: Commands) {
+    commands.remove_resource::<ActiveTutorial>();
+}
+
+fn complete_step(tutorial: &mut ActiveTutorial, save_data: &mut SaveData, commands: &mut Commands) {
+    tutorial.current += 1;
+    if tutorial.step().is_none() {
+        finish_tutorial(tutorial.level, save_data, commands);
+    }
+}
+
+// Finished and skipped tutorials are both remembered, neither is shown again
+fn finish_tutorial(level: usize, save_data: &mut SaveData, commands: &mut Commands) {
+    let completed = &mut save_data.tutorials.completed_levels;
+    if !completed.contains(&level) {
+        completed.push(level);
+    }
+    commands.remove_resource::<ActiveTutorial>();
+}
+
+fn advance_tutorial(
+    mut tutorial: ResMut<ActiveTutorial>,
+    mut reactions: EventReader<ReactionTriggered>,
+    towers: Query<&Tower, Without<Preplaced>>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    let done = match tutorial.goal() {
+        Some(TutorialGoal::PlaceTower(tower)) => towers.iter().any(|w| *w == tower),
+        Some(TutorialGoal::Reaction(reaction)) => reactions.read().any(|w| w.reaction == reaction),
+        _ => false,
+    };
+    // Reactions from before the step came up don't count
+    reactions.clear();
+
+    if done {
+        complete_step(&mut tutorial, &mut save_data, &mut commands);
+    }
+}
+
+fn complete_wave_step(
+    _: Trigger<WaveCalled>,
+    tutorial: Option<ResMut<ActiveTutorial>>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    if tutorial.goal() == Some(TutorialGoal::StartWave) {
+        complete_step(&mut tutorial, &mut save_data, &mut commands);
+    }
+}
+
+fn acknowledge_step(
+    _: Trigger<Pointer<Click>>,
+    tutorial: Option<ResMut<ActiveTutorial>>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    let Some(mut tutorial) = tutorial else {
+        return;
+    };
+    if tutorial.goal() == Some(TutorialGoal::Acknowledge) {
+        complete_step(&mut tutorial, &mut save_data, &mut commands);
+    }
+}
+
+fn skip_tutorial(
+    _: Trigger<Pointer<Click>>,
+    tutorial: Option<Res<ActiveTutorial>>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    if let Some(tutorial) = tutorial {
+        finish_tutorial(tutorial.level, &mut save_data, &mut commands);
+    }
+}
+
+fn clear_tutorial_step(
+    spawned: Query<Entity, Or<(With<TutorialBubble>, With<TutorialHighlight>)>>,
+    outlined: Query<Entity, With<TutorialOutlined>>,
+    mut commands: Commands,
+) {
+    for entity in spawned.iter() {
+        commands.entity(entity).despawn();
+    }
+    for entity in outlined.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Outline, TutorialOutlined)>();
+    }
+}
+
+fn show_tutorial_step(
+    tutorial: Res<ActiveTutorial>,
+    anchors: Query<(Entity, &TutorialAnchor)>,
+    mut commands: Commands,
+) {
+    let Some(step) = tutorial.step() else {
+        return;
+    };
+
+    match step.target {
+        TutorialTarget::None => {}
+        TutorialTarget::Ui(target) => {
+            for (entity, _) in anchors.iter().filter(|(_, w)| w.0 == target) {
+                commands.entity(entity).insert((
+                    Outline::new(Val::Px(3.0), Val::Px(2.0), tailwind::AMBER_400.into()),
+                    TutorialOutlined,
+                ));
+            }
+        }
+        TutorialTarget::Cell(cell) => {
+            commands.spawn((
+                Name::new("Tutorial Highlight"),
+                TutorialHighlight,
+                StateScoped(Screen::Gameplay),
+                Sprite::from_color(
+                    tailwind::AMBER_400.with_alpha(0.35),
+                    Vec2::splat(LEVEL_SCALING),
+                ),
+                Transform::from_translation((cell.as_vec2() * LEVEL_SCALING).extend(1.0)),
+            ));
+        }
+    }
+
+    let acknowledge = step.goal == TutorialGoal::Acknowledge;
+    commands.spawn((
+        Name::new("Tutorial Bubble"),
+        TutorialBubble,
+        StateScoped(Screen::Gameplay),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(72.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        children![(
+            Node {
+                max_width: Val::Px(520.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.85)),
+            BorderColor(tailwind::AMBER_400.into()),
+            BorderRadius::all(Val::Px(8.0)),
+            children![
+                widget::label(format!(
+                    "Tutorial {}/{}",
+                    tutorial.current + 1,
+                    tutorial.steps.len()
+                )),
+                widget::body_text(step.text),
+                (
+                    Node {
+                        column_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                    Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                        if acknowledge {
+                            parent.spawn(widget::button_medium("Got it", acknowledge_step));
+                        }
+                        parent.spawn(widget::button_medium("Skip tutorial", skip_tutorial));
+                    })),
+                ),
+            ],
+        )],
+    ));
+}
@@ -16,12 +16,16 @@ use bevy_composable::{
     app_impl::{ComplexSpawnable, ComponentTreeable},
     tree::ComponentTree,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, time::Duration};
 
 use crate::{
     PausableSystems,
     assets::UiAssets,
-    data::levels::LevelData,
+    data::{levels::LevelData, tutorials::TutorialUi},
+    demo::enemy_health::EnemyHealth,
+    gameplay::{economy::WaveCalled, tutorial::TutorialAnchor},
+    input::{Action, action_just_pressed},
     level::components::StartNode,
     prefabs::enemies::{basic_trooper, chonkus_trooper, turbo_trooper},
     prelude::*,
@@ -66,6 +70,13 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(spawn_leave_observer);
     app.add_observer(spawn_pressed_observer);
     app.add_observer(spawn_released_observer);
+    app.add_systems(
+        Update,
+        call_wave_with_action
+            .run_if(action_just_pressed(Action::NextWave))
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
     app.add_systems(
         Update,
         wave_spawn_button_mouse_out.run_if(in_state(MouseSpawnBtn::Out)),
@@ -84,18 +95,20 @@ pub fn add_waves(
     level_data: Res<LevelData>,
     level_select: Res<LevelSelect>,
 ) {
-    let default_set = &test_waves();
-    let next_wave_set = level_data
-        .enemies
-        .get(level_select.0)
-        .unwrap_or(default_set);
-
     *wave_manager = WaveManager {
-        upcoming_waves: next_wave_set.clone(),
+        upcoming_waves: level_waves(&level_data, level_select.0),
         ..Default::default()
     };
 }
 
+pub fn level_waves(level_data: &LevelData, level: usize) -> VecDeque<Wave> {
+    level_data
+        .enemies
+        .get(level)
+        .cloned()
+        .unwrap_or_else(test_waves)
+}
+
 pub fn tick_wave_timer(mut wave_manager: ResMut<WaveManager>, time: Res<Time>) {
     wave_manager.wave_timer.tick(time.delta());
 }
@@ -132,10 +145,53 @@ impl Default for WaveManager {
     }
 }
 
+// Where a level's waves are up to, without the enemies themselves
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WaveProgress {
+    pub waves_started: usize,
+    // Groups of the current wave still to spawn, None between waves
+    pub groups_left: Option<usize>,
+    pub timer_elapsed: f32,
+    pub timer_duration: f32,
+}
+
 impl WaveManager {
     pub fn remaining_waves(&self) -> usize {
         self.upcoming_waves.len()
     }
+
+    pub fn progress(&self, total_waves: usize) -> WaveProgress {
+        WaveProgress {
+            waves_started: total_waves.saturating_sub(self.upcoming_waves.len()),
+            groups_left: self.current_wave.as_ref().map(|w| w.0.len()),
+            timer_elapsed: self.wave_timer.elapsed_secs(),
+            timer_duration: self.wave_timer.duration().as_secs_f32(),
+        }
+    }
+
+    // Rebuilds the manager from a level's full wave list
+    pub fn restore(mut waves: VecDeque<Wave>, progress: WaveProgress) -> Self {
+        let mut last_started = None;
+        for _ in 0..progress.waves_started {
+            last_started = waves.pop_front();
+        }
+        let current_wave = match (last_started, progress.groups_left) {
+            (Some(mut wave), Some(groups_left)) => {
+                let spawned = wave.0.len().saturating_sub(groups_left);
+                wave.0.drain(..spawned);
+                Some(wave)
+            }
+            _ => None,
+        };
+
+        let mut wave_timer = Timer::from_seconds(progress.timer_duration, TimerMode::Once);
+        wave_timer.set_elapsed(Duration::from_secs_f32(progress.timer_elapsed));
+        Self {
+            current_wave,
+            upcoming_waves: waves,
+            wave_timer,
+        }
+    }
 }
 
 impl From<Vec<ComponentTree>> for Group {
@@ -181,6 +237,7 @@ fn spawn_wave_ui(icon: Handle<Image>, layout: Handle<TextureAtlasLayout>) -> imp
             },
             Pickable::default(),
             SpawnButtonMarker,
+            TutorialAnchor(TutorialUi::NextWave),
             ImageNode {
                 image: icon,
                 texture_atlas: Some(TextureAtlas {
@@ -258,6 +315,8 @@ fn spawn_released_observer(
     mut goto_next_level: EventWriter<GotoNextLevel>,
     mut spawn_button_marker: Query<&mut ImageNode, With<SpawnButtonMarker>>,
     mut wave_manager: ResMut<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut commands: Commands,
 ) {
     let Ok(mut image_node) = spawn_button_marker.get_mut(trigger.target) else {
         return;
@@ -266,11 +325,43 @@ fn spawn_released_observer(
         atlas.index = 3;
     }
 
+    call_next_wave(
+        &mut wave_manager,
+        &mut goto_next_level,
+        &enemies,
+        &mut commands,
+    );
+}
+
+fn call_wave_with_action(
+    mut goto_next_level: EventWriter<GotoNextLevel>,
+    mut wave_manager: ResMut<WaveManager>,
+    enemies: Query<(), With<EnemyHealth>>,
+    mut commands: Commands,
+) {
+    call_next_wave(
+        &mut wave_manager,
+        &mut goto_next_level,
+        &enemies,
+        &mut commands,
+    );
+}
+
+// Moves on to the next level once every wave has been called
+fn call_next_wave(
+    wave_manager: &mut WaveManager,
+    goto_next_level: &mut EventWriter<GotoNextLevel>,
+    enemies: &Query<(), With<EnemyHealth>>,
+    commands: &mut Commands,
+) {
     if wave_manager.current_wave.is_none() && wave_manager.remaining_waves() == 0 {
         goto_next_level.write(GotoNextLevel(0));
     } else {
         if wave_manager.current_wave.is_none() {
             wave_manager.current_wave = wave_manager.upcoming_waves.pop_front();
+            commands.trigger(WaveCalled {
+                early: !enemies.is_empty(),
+            });
         }
     }
 }
@@ -333,3 +424,62 @@ pub fn test_waves() -> VecDeque<Wave> {
     ]
     .into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn waves(count: usize, groups: usize) -> VecDeque<Wave> {
+        (0..count)
+            .map(|_| {
+                Wave(
+                    (0..groups)
+                        .map(|_| (Group(vec![]), Duration::from_secs(1)))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn restores_mid_wave() {
+        let progress = WaveProgress {
+            waves_started: 2,
+            groups_left: Some(1),
+            timer_elapsed: 0.25,
+            timer_duration: 1.,
+        };
+        let manager = WaveManager::restore(waves(4, 3), progress);
+
+        assert_eq!(manager.remaining_waves(), 2);
+        assert_eq!(manager.current_wave.as_ref().map(|w| w.0.len()), Some(1));
+        assert_eq!(manager.wave_timer.elapsed_secs(), 0.25);
+    }
+
+    #[test]
+    fn restores_between_waves() {
+        let progress = WaveProgress {
+            waves_started: 1,
+            groups_left: None,
+            timer_elapsed: 0.,
+            timer_duration: 1.,
+        };
+        let manager = WaveManager::restore(waves(3, 2), progress);
+
+        assert_eq!(manager.remaining_waves(), 2);
+        assert!(manager.current_wave.is_none());
+    }
+
+    #[test]
+    fn progress_round_trips() {
+        let progress = WaveProgress {
+            waves_started: 3,
+            groups_left: Some(2),
+            timer_elapsed: 0.5,
+            timer_duration: 1.,
+        };
+        let manager = WaveManager::restore(waves(5, 4), progress);
+
+        assert_eq!(manager.progress(5), progress);
+    }
+}
@@ -0,0 +1,341 @@
+//! Player actions and the keys bound to them.
+//!
+//! Systems ask about an [`Action`] rather than a `KeyCode`, so bindings can be
+//! changed from the controls menu and kept in the save data. Gamepad buttons
+//! trigger the same actions but aren't rebindable.
+
+use std::collections::HashMap;
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, Enum, FromReflect},
+};
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InputMap>();
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum Action {
+    Pause,
+    Back,
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    // Held with clicks and the mouse to place several towers or zoom
+    Modifier,
+    // Held with undo and redo
+    Command,
+    Undo,
+    Redo,
+    Rotate,
+    AimFan,
+    Sell,
+    NextWave,
+    HotbarPrevious,
+    HotbarNext,
+    Hotbar1,
+    Hotbar2,
+    Hotbar3,
+    Hotbar4,
+    Hotbar5,
+    Hotbar6,
+    Hotbar7,
+    Hotbar8,
+    Hotbar9,
+    Hotbar10,
+    DebugUi,
+    AddMoney,
+    SkipLevel,
+    CombatLog,
+    ExportCombatLog,
+}
+
+impl Action {
+    pub fn all() -> Vec<Action> {
+        let mut actions = vec![
+            Action::Pause,
+            Action::Back,
+            Action::Up,
+            Action::Down,
+            Action::Left,
+            Action::Right,
+            Action::Confirm,
+            Action::Modifier,
+            Action::Command,
+            Action::Undo,
+            Action::Redo,
+            Action::Rotate,
+            Action::AimFan,
+            Action::Sell,
+            Action::NextWave,
+            Action::HotbarPrevious,
+            Action::HotbarNext,
+        ];
+        actions.extend(Self::HOTBAR);
+        actions.extend([
+            Action::DebugUi,
+            Action::AddMoney,
+            Action::SkipLevel,
+            Action::CombatLog,
+            Action::ExportCombatLog,
+        ]);
+        actions
+    }
+
+    // Hotbar slots from the left, the cheapest tower first
+    pub const HOTBAR: [Action; 10] = [
+        Action::Hotbar1,
+        Action::Hotbar2,
+        Action::Hotbar3,
+        Action::Hotbar4,
+        Action::Hotbar5,
+        Action::Hotbar6,
+        Action::Hotbar7,
+        Action::Hotbar8,
+        Action::Hotbar9,
+        Action::Hotbar10,
+    ];
+
+    pub fn name(&self) -> String {
+        match self {
+            Action::Pause => "Pause".to_string(),
+            Action::Back => "Back / cancel".to_string(),
+            Action::Up => "Up".to_string(),
+            Action::Down => "Down".to_string(),
+            Action::Left => "Left".to_string(),
+            Action::Right => "Right".to_string(),
+            Action::Confirm => "Confirm".to_string(),
+            Action::Modifier => "Place several / drag zoom".to_string(),
+            Action::Command => "Undo and redo modifier".to_string(),
+            Action::Undo => "Undo".to_string(),
+            Action::Redo => "Redo".to_string(),
+            Action::Rotate => "Rotate tower".to_string(),
+            Action::AimFan => "Aim fan".to_string(),
+            Action::Sell => "Sell tower".to_string(),
+            Action::NextWave => "Next wave".to_string(),
+            Action::HotbarPrevious => "Previous tower".to_string(),
+            Action::HotbarNext => "Next tower".to_string(),
+            Action::DebugUi => "Debug UI".to_string(),
+            Action::AddMoney => "Add money".to_string(),
+            Action::SkipLevel => "Skip level".to_string(),
+            Action::CombatLog => "Combat log".to_string(),
+            Action::ExportCombatLog => "Export combat log".to_string(),
+            hotbar => {
+                let slot = Self::HOTBAR.iter().position(|w| w == hotbar).unwrap_or(0);
+                format!("Hotbar slot {}", slot + 1)
+            }
+        }
+    }
+
+    // Only offered for rebinding in dev builds
+    pub fn is_dev(&self) -> bool {
+        matches!(
+            self,
+            Action::DebugUi
+                | Action::AddMoney
+                | Action::SkipLevel
+                | Action::CombatLog
+                | Action::ExportCombatLog
+        )
+    }
+
+    pub fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            Action::Pause => vec![KeyCode::Space],
+            Action::Back => vec![KeyCode::Escape],
+            Action::Up => vec![KeyCode::ArrowUp, KeyCode::KeyW],
+            Action::Down => vec![KeyCode::ArrowDown, KeyCode::KeyS],
+            Action::Left => vec![KeyCode::ArrowLeft, KeyCode::KeyA],
+            Action::Right => vec![KeyCode::ArrowRight, KeyCode::KeyD],
+            Action::Confirm => vec![KeyCode::Enter],
+            Action::Modifier => vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            Action::Command => vec![
+                KeyCode::ControlLeft,
+                KeyCode::ControlRight,
+                KeyCode::SuperLeft,
+                KeyCode::SuperRight,
+            ],
+            Action::Undo => vec![KeyCode::KeyZ],
+            Action::Redo => vec![KeyCode::KeyY],
+            Action::Rotate => vec![KeyCode::KeyR],
+            Action::AimFan => vec![KeyCode::KeyF],
+            Action::Sell => vec![KeyCode::Delete, KeyCode::KeyX],
+            Action::NextWave => vec![KeyCode::KeyG],
+            Action::HotbarPrevious => vec![KeyCode::KeyQ],
+            Action::HotbarNext => vec![KeyCode::KeyE],
+            Action::Hotbar1 => vec![KeyCode::Digit1],
+            Action::Hotbar2 => vec![KeyCode::Digit2],
+            Action::Hotbar3 => vec![KeyCode::Digit3],
+            Action::Hotbar4 => vec![KeyCode::Digit4],
+            Action::Hotbar5 => vec![KeyCode::Digit5],
+            Action::Hotbar6 => vec![KeyCode::Digit6],
+            Action::Hotbar7 => vec![KeyCode::Digit7],
+            Action::Hotbar8 => vec![KeyCode::Digit8],
+            Action::Hotbar9 => vec![KeyCode::Digit9],
+            Action::Hotbar10 => vec![KeyCode::Digit0],
+            Action::DebugUi => vec![KeyCode::Backquote],
+            Action::AddMoney => vec![KeyCode::KeyM],
+            Action::SkipLevel => vec![KeyCode::KeyN],
+            Action::CombatLog => vec![KeyCode::KeyL],
+            Action::ExportCombatLog => vec![KeyCode::KeyK],
+        }
+    }
+
+    pub fn gamepad_buttons(&self) -> &'static [GamepadButton] {
+        match self {
+            Action::Pause => &[GamepadButton::Start],
+            Action::Back => &[GamepadButton::East],
+            Action::Up => &[GamepadButton::DPadUp],
+            Action::Down => &[GamepadButton::DPadDown],
+            Action::Left => &[GamepadButton::DPadLeft],
+            Action::Right => &[GamepadButton::DPadRight],
+            Action::Confirm => &[GamepadButton::South],
+            Action::Sell => &[GamepadButton::West],
+            Action::NextWave => &[GamepadButton::North],
+            Action::HotbarPrevious => &[GamepadButton::LeftTrigger],
+            Action::HotbarNext => &[GamepadButton::RightTrigger],
+            Action::Rotate => &[GamepadButton::RightTrigger2],
+            Action::AimFan => &[GamepadButton::LeftTrigger2],
+            _ => &[],
+        }
+    }
+}
+
+// Keys are saved by name, `KeyCode` itself can't be serialized
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(
+    from = "HashMap<Action, Vec<String>>",
+    into = "HashMap<Action, Vec<String>>"
+)]
+pub struct InputMap {
+    bindings: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::all()
+                .into_iter()
+                .map(|w| (w, w.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+impl From<HashMap<Action, Vec<String>>> for InputMap {
+    // Actions missing from older saves keep their default keys
+    fn from(saved: HashMap<Action, Vec<String>>) -> Self {
+        let mut map = Self::default();
+        for (action, names) in saved {
+            let keys = names.iter().filter_map(|w| key_from_name(w)).collect();
+            map.bindings.insert(action, keys);
+        }
+        map
+    }
+}
+
+impl From<InputMap> for HashMap<Action, Vec<String>> {
+    fn from(map: InputMap) -> Self {
+        map.bindings
+            .into_iter()
+            .map(|(action, keys)| (action, keys.iter().map(key_name).collect()))
+            .collect()
+    }
+}
+
+impl InputMap {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.bindings.get(&action).map_or(&[], |w| w.as_slice())
+    }
+
+    // Makes `key` the only binding of `action`, returning the actions it was taken from
+    pub fn bind(&mut self, action: Action, key: KeyCode) -> Vec<Action> {
+        let mut unbound = Vec::new();
+        for (other, keys) in self.bindings.iter_mut() {
+            if *other != action && keys.contains(&key) {
+                keys.retain(|w| *w != key);
+                unbound.push(*other);
+            }
+        }
+        self.bindings.insert(action, vec![key]);
+        unbound
+    }
+
+    // Other actions that share a key with `action`
+    pub fn conflicts(&self, action: Action) -> Vec<Action> {
+        let keys = self.keys(action);
+        Action::all()
+            .into_iter()
+            .filter(|w| *w != action && self.keys(*w).iter().any(|k| keys.contains(k)))
+            .collect()
+    }
+}
+
+// Reads actions through the current bindings and any connected gamepad
+#[derive(SystemParam)]
+pub struct Actions<'w, 's> {
+    map: Res<'w, InputMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl Actions<'_, '_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.keys.any_pressed(self.map.keys(action).iter().copied())
+            || self
+                .gamepads
+                .iter()
+                .any(|w| w.any_pressed(action.gamepad_buttons().iter().copied()))
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.keys
+            .any_just_pressed(self.map.keys(action).iter().copied())
+            || self
+                .gamepads
+                .iter()
+                .any(|w| w.any_just_pressed(action.gamepad_buttons().iter().copied()))
+    }
+}
+
+// Run condition, the `input_just_pressed` of actions
+pub fn action_just_pressed(action: Action) -> impl FnMut(Actions) -> bool + Clone {
+    move |actions: Actions| actions.just_pressed(action)
+}
+
+pub fn key_name(key: &KeyCode) -> String {
+    key.variant_name().to_string()
+}
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    KeyCode::from_reflect(&DynamicEnum::new(name.to_string(), DynamicVariant::Unit))
+}
+
+// How a key is shown to the player, `KeyA` as `A` and `Digit1` as `1`
+pub fn key_label(key: &KeyCode) -> String {
+    let name = key_name(key);
+    match *key {
+        KeyCode::Backquote => "`".to_string(),
+        KeyCode::ArrowUp => "Up".to_string(),
+        KeyCode::ArrowDown => "Down".to_string(),
+        KeyCode::ArrowLeft => "Left".to_string(),
+        KeyCode::ArrowRight => "Right".to_string(),
+        _ => name
+            .strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name)
+            .to_string(),
+    }
+}
+
+pub fn keys_label(keys: &[KeyCode]) -> String {
+    if keys.is_empty() {
+        return "Unbound".to_string();
+    }
+    keys.iter().map(key_label).collect::<Vec<_>>().join(" / ")
+}
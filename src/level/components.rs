@@ -6,6 +6,7 @@ use avian2d::prelude::{Collider, CollisionLayers, Friction, RigidBody};
 use bevy::prelude::*;
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
 use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 
 pub const WALL_TOTAL_WIDTH: f32 = 0.10;
@@ -13,7 +14,7 @@ pub const WALL_PICKABLE_WIDTH: f32 = 0.20;
 pub const FLOOR_TOTAL_HEIGHT: f32 = 0.10;
 pub const LEVEL_SCALING: f32 = 10.;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum WallDirection {
     Left,
     Right,
@@ -46,13 +47,17 @@ pub struct StartNode;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Component, Reflect)]
 pub struct EndNode;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+// How far along the path a node is, the start node is 0
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Component, Reflect)]
+pub struct PathIndex(pub usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum GeneralPosition {
     UpDown,
     LeftRight,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum ExactPosition {
     Floor,
     Ceiling,
@@ -65,14 +70,38 @@ pub struct Adjacent {
     pub exact_position: ExactPosition,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Component, Reflect)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Component, Reflect, Serialize, Deserialize)]
 pub struct AdjacentId {
     pub unit_x: usize,
     pub unit_y: usize,
     pub general_position: GeneralPosition,
 }
 
+impl ExactPosition {
+    // The direction from a tower mounted on this piece towards the piece
+    pub fn mount_direction(&self) -> CellDirection {
+        match self {
+            ExactPosition::Floor => CellDirection::Down,
+            ExactPosition::Ceiling => CellDirection::Up,
+            ExactPosition::Wall(WallDirection::Left) => CellDirection::Left,
+            ExactPosition::Wall(WallDirection::Right) => CellDirection::Right,
+        }
+    }
+}
+
 impl Adjacent {
+    // The level cell this piece faces, walls and floors belong to the cell on their open side
+    pub fn cell(&self) -> IVec2 {
+        let x = self.id.unit_x as i32;
+        let y = self.id.unit_y as i32;
+        match self.exact_position {
+            ExactPosition::Wall(WallDirection::Right) => IVec2::new(x - 1, y),
+            ExactPosition::Wall(WallDirection::Left) => IVec2::new(x, y),
+            ExactPosition::Ceiling => IVec2::new(x, y - 1),
+            ExactPosition::Floor => IVec2::new(x, y),
+        }
+    }
+
     pub fn new(
         unit_x: usize,
         unit_y: usize,
@@ -183,6 +212,7 @@ impl LevelParent {
                 start_node.1,
                 last_direction,
             ) + StartNode.store()
+                + PathIndex(0).store()
                 + AnimationFrameQueue::new(&[0, 1, 2, 3, 4]).store()
                 + Sprite {
                     image: level_assets.enemy_spawner.clone(),
@@ -202,9 +232,10 @@ impl LevelParent {
                 last_node.0.y * LEVEL_SCALING,
                 last_node.1,
                 last_node.1,
-            ) + EndNode.store());
+            ) + EndNode.store()
+                + PathIndex(level_data.path.len() - 1).store());
 
-        for node_i in path_iter {
+        for (i, node_i) in path_iter.enumerate() {
             let (pos, direction) = node_i;
             level = level
                 << node(
@@ -212,7 +243,7 @@ impl LevelParent {
                     pos.y * LEVEL_SCALING,
                     *direction,
                     last_direction,
-                );
+                ) + PathIndex(i + 1).store();
             last_direction = *direction;
         }
 
@@ -1,5 +1,6 @@
 use crate::data::Tower;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Resource, Clone, Copy, Debug, Hash, Reflect, PartialEq, Eq)]
 pub struct CurrentLoadedLevel(pub usize);
@@ -13,9 +14,17 @@ pub struct UnlockedLevels(pub Vec<usize>);
 #[derive(Event, Debug, Hash, PartialEq, Eq, Clone, Reflect)]
 pub struct GotoNextLevel(pub usize);
 
+// Fired once when the last wave of a level has been cleared
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct LevelCompleted {
+    pub level: usize,
+    pub lives: i32,
+    pub stars: u8,
+}
+
 // Which direction the enemies need to move in. If we end up adding splitting paths, this won't be
 // usable. It's mainly to help get a FWP going.
-#[derive(Component, Clone, Copy, Debug, Hash, Reflect, PartialEq, Eq)]
+#[derive(Component, Clone, Copy, Debug, Hash, Reflect, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CellDirection {
     Up,
     Down,
@@ -13,9 +13,12 @@ mod demo;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod gameplay;
+mod input;
 mod level;
 mod menus;
+mod persistence;
 mod prefabs;
+mod save_data;
 mod screens;
 mod theme;
 mod utils;
@@ -26,6 +29,8 @@ use bevy::{
     prelude::*,
 };
 use bevy_turborand::prelude::RngPlugin;
+use data::PointerInteractionState;
+use input::{Action, Actions};
 
 pub mod prelude {
     pub use crate::assets::{GameAssets, UiAssets};
@@ -39,6 +44,8 @@ const WINDOW_X: f32 = 1280.0;
 const WINDOW_Y: f32 = 720.0;
 const MAX_ZOOM_OUT: f32 = 2.5;
 const MAX_ZOOM_IN: f32 = 0.5;
+const GAMEPAD_PAN_SPEED: f32 = 60.0;
+const GAMEPAD_ZOOM_SPEED: f32 = 100.0;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
@@ -74,9 +81,11 @@ impl Plugin for AppPlugin {
             demo::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            input::plugin,
             level::plugin,
             menus::plugin,
             prefabs::plugin,
+            save_data::plugin,
             screens::plugin,
             theme::plugin,
             RngPlugin::default(),
@@ -103,7 +112,7 @@ impl Plugin for AppPlugin {
         // Camera controls
         app.add_systems(
             Update,
-            cameraman
+            (cameraman, gamepad_camera)
                 .run_if(in_state(crate::prelude::Screen::Gameplay))
                 .in_set(PausableSystems),
         );
@@ -148,9 +157,10 @@ fn spawn_camera(mut commands: Commands) {
 fn cameraman(
     mut camera: Query<&mut Transform, With<Camera2d>>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: Actions,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
     accumulated_mouse_motion: Res<AccumulatedMouseMotion>,
+    pointer_state: Res<State<PointerInteractionState>>,
 ) {
     let Ok(mut camera_transform) = camera.single_mut() else {
         return;
@@ -160,7 +170,7 @@ fn cameraman(
         let delta = accumulated_mouse_motion.delta;
         // In a browser, the scroll wheel might not work. So instead override
         // movement when pressing shift to do zooming.
-        if keys.pressed(KeyCode::ShiftLeft) {
+        if actions.pressed(Action::Modifier) {
             camera_transform.scale = zoom(delta.y, camera_transform.scale);
         } else {
             camera_transform.translation += Vec2::new(-delta.x / 15., delta.y / 15.).extend(0.0);
@@ -171,6 +181,10 @@ fn cameraman(
         match mouse_wheel_event.unit {
             MouseScrollUnit::Line => {
                 // Case 1: Use is using a scroll wheel
+                // The wheel rotates towers while placing them
+                if matches!(pointer_state.get(), PointerInteractionState::Placing(_)) {
+                    continue;
+                }
                 let dy = mouse_wheel_event.y;
                 camera_transform.scale = zoom(dy, camera_transform.scale);
             }
@@ -180,7 +194,7 @@ fn cameraman(
                 let dy = mouse_wheel_event.y;
                 let dx = mouse_wheel_event.x;
 
-                if keys.pressed(KeyCode::ShiftLeft) {
+                if actions.pressed(Action::Modifier) {
                     camera_transform.scale = zoom(dy, camera_transform.scale);
                 } else {
                     camera_transform.translation += Vec2::new(-dx / 35., dy / 35.).extend(0.0);
@@ -190,6 +204,30 @@ fn cameraman(
     }
 }
 
+// The left stick pans and the right stick zooms
+fn gamepad_camera(
+    mut camera: Query<&mut Transform, With<Camera2d>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+) {
+    let Ok(mut camera_transform) = camera.single_mut() else {
+        return;
+    };
+
+    for gamepad in gamepads.iter() {
+        let pan = gamepad.left_stick() * GAMEPAD_PAN_SPEED * time.delta_secs();
+        camera_transform.translation += (pan * camera_transform.scale.x).extend(0.0);
+
+        let zoom_input = gamepad.right_stick().y;
+        if zoom_input != 0.0 {
+            camera_transform.scale = zoom(
+                zoom_input * GAMEPAD_ZOOM_SPEED * time.delta_secs(),
+                camera_transform.scale,
+            );
+        }
+    }
+}
+
 fn zoom(scaler: f32, current_scale: Vec3) -> Vec3 {
     let final_scale = current_scale - Vec2::splat(scaler / 100.).extend(0.0);
     if final_scale.x > MAX_ZOOM_OUT {
@@ -0,0 +1,292 @@
+//! The codex, listing every tower, status effect and reaction.
+
+use bevy::{color::palettes::tailwind, ecs::spawn::SpawnWith, prelude::*, ui::Val::*};
+
+use crate::{
+    assets::{StatusSprites, UiAssets},
+    data::{
+        Tower,
+        projectiles::{AttackSpecification, LiquidType, TowerAttackType},
+        status_effects::{Reaction, StatusEnum, status_debuffs},
+    },
+    input::{Action, action_just_pressed},
+    menus::Menu,
+    save_data::SaveData,
+    screens::Screen,
+    theme::{palette::LABEL_TEXT, prelude::*},
+};
+
+const ENTRY_WIDTH: f32 = 290.0;
+const ICON_SIZE: f32 = 48.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CodexTab>();
+    app.add_systems(OnEnter(Menu::Codex), spawn_codex_menu);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(action_just_pressed(Action::Back)),
+            show_codex_entries.run_if(resource_changed::<CodexTab>),
+        )
+            .run_if(in_state(Menu::Codex)),
+    );
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum CodexTab {
+    #[default]
+    Towers,
+    StatusEffects,
+    Reactions,
+}
+
+#[derive(Component)]
+struct CodexEntries;
+
+fn spawn_codex_menu(mut commands: Commands, mut tab: ResMut<CodexTab>) {
+    // Entries are filled in by `show_codex_entries`
+    tab.set_changed();
+
+    commands.spawn((
+        widget::ui_root("Codex Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Codex),
+        children![
+            (
+                Name::new("Codex Tabs"),
+                Node {
+                    column_gap: Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button_medium("Towers", show_towers),
+                    widget::button_medium("Status Effects", show_status_effects),
+                    widget::button_medium("Reactions", show_reactions),
+                ],
+            ),
+            (
+                Name::new("Codex Entries"),
+                CodexEntries,
+                Node {
+                    display: Display::Grid,
+                    row_gap: Px(12.0),
+                    column_gap: Px(12.0),
+                    grid_template_columns: RepeatedGridTrack::px(4, ENTRY_WIDTH),
+                    ..default()
+                },
+            ),
+            widget::button_medium("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn show_towers(_: Trigger<Pointer<Click>>, mut tab: ResMut<CodexTab>) {
+    *tab = CodexTab::Towers;
+}
+
+fn show_status_effects(_: Trigger<Pointer<Click>>, mut tab: ResMut<CodexTab>) {
+    *tab = CodexTab::StatusEffects;
+}
+
+fn show_reactions(_: Trigger<Pointer<Click>>, mut tab: ResMut<CodexTab>) {
+    *tab = CodexTab::Reactions;
+}
+
+// A codex entry before it is laid out: icon, title and lines of detail
+struct Entry {
+    icon: ImageNode,
+    title: String,
+    title_color: Color,
+    lines: Vec<String>,
+}
+
+fn show_codex_entries(
+    tab: Res<CodexTab>,
+    containers: Query<Entity, With<CodexEntries>>,
+    ui_assets: Res<UiAssets>,
+    status_sprites: Res<StatusSprites>,
+    save_data: Res<SaveData>,
+    mut commands: Commands,
+) {
+    let Ok(container) = containers.single() else {
+        return;
+    };
+
+    let entries: Vec<_> = match *tab {
+        CodexTab::Towers => Tower::all()
+            .into_iter()
+            .map(|w| tower_entry(w, &ui_assets))
+            .collect(),
+        CodexTab::StatusEffects => StatusEnum::all()
+            .into_iter()
+            .map(|w| status_entry(w, &status_sprites))
+            .collect(),
+        CodexTab::Reactions => Reaction::all()
+            .into_iter()
+            .map(|w| reaction_entry(w, save_data.discovered(w), &status_sprites))
+            .collect(),
+    };
+
+    commands
+        .entity(container)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for entry in entries {
+                parent.spawn(entry_bundle(entry));
+            }
+        });
+}
+
+fn status_icon(status: StatusEnum, sprites: &StatusSprites) -> ImageNode {
+    let (image, layout) = sprites.status_sprite(status);
+    ImageNode::from_atlas_image(image.clone(), TextureAtlas::from(layout.clone()))
+}
+
+fn tower_entry(tower: Tower, ui_assets: &UiAssets) -> Entry {
+    let icon = ui_assets
+        .hotbar_icons
+        .get(tower.ui_asset_key())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut numbers = vec![format!("Cost {}", tower.price())];
+    if tower.cooldown() > 0. {
+        numbers.push(format!("every {:.2}s", tower.cooldown()));
+    }
+    if let Some(range) = tower.range() {
+        numbers.push(format!("range {range}"));
+    }
+
+    Entry {
+        icon: ImageNode::new(icon).with_color(tower.tint()),
+        title: tower.name().to_string(),
+        title_color: LABEL_TEXT,
+        lines: vec![
+            tower.description().to_string(),
+            numbers.join(", "),
+            attack_summary(&tower.attack_def()),
+        ],
+    }
+}
+
+fn attack_summary(attack: &TowerAttackType) -> String {
+    let specs = match attack {
+        TowerAttackType::EntireCell(specs)
+        | TowerAttackType::Contact(specs)
+        | TowerAttackType::Projectile(specs) => specs,
+        TowerAttackType::DropsLiquid(liquid) => {
+            let liquid = match liquid {
+                LiquidType::Water => "water",
+                LiquidType::Oil => "oil",
+                LiquidType::Acid => "acid",
+            };
+            return format!("Drops puddles of {liquid}");
+        }
+        TowerAttackType::ModifiesSelf => return "Opens beneath enemies".to_string(),
+    };
+    specs
+        .iter()
+        .map(|spec| match spec {
+            AttackSpecification::Damage(damage_type, amount) => {
+                format!("{amount} {damage_type:?} damage")
+            }
+            AttackSpecification::Push(force) => format!("push {force}"),
+            AttackSpecification::Status(status) => format!("applies {}", status.name()),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn status_entry(status: StatusEnum, sprites: &StatusSprites) -> Entry {
+    let mut lines = vec![format!("Lasts {}s", status.base_duration())];
+    lines.extend(
+        status_debuffs(status).map(|(stat, multiplier)| format!("{} x{multiplier}", stat.name())),
+    );
+    Entry {
+        icon: status_icon(status, sprites),
+        title: status.name().to_string(),
+        title_color: status.color(),
+        lines,
+    }
+}
+
+fn reaction_entry(reaction: Reaction, discovered: bool, sprites: &StatusSprites) -> Entry {
+    let icon = status_icon(reaction.icon_status(), sprites);
+    if discovered {
+        Entry {
+            icon,
+            title: reaction.name().to_string(),
+            title_color: LABEL_TEXT,
+            lines: vec![reaction.recipe().to_string()],
+        }
+    } else {
+        Entry {
+            icon: icon.with_color(Color::BLACK.with_alpha(0.6)),
+            title: "???".to_string(),
+            title_color: tailwind::SLATE_400.into(),
+            lines: vec!["Trigger this reaction in a level to discover it.".to_string()],
+        }
+    }
+}
+
+fn entry_bundle(entry: Entry) -> impl Bundle {
+    let Entry {
+        icon,
+        title,
+        title_color,
+        lines,
+    } = entry;
+    (
+        Name::new("Codex Entry"),
+        Node {
+            column_gap: Px(8.0),
+            padding: UiRect::all(Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.5)),
+        BorderRadius::all(Px(8.0)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn((
+                Node {
+                    width: Px(ICON_SIZE),
+                    height: Px(ICON_SIZE),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+                icon,
+            ));
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(2.0),
+                    ..default()
+                })
+                .with_children(|column| {
+                    column.spawn((widget::label(title), TextColor(title_color)));
+                    for line in lines {
+                        column.spawn(widget::body_text(line));
+                    }
+                });
+        })),
+    )
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
@@ -0,0 +1,225 @@
+//! The controls menu, where actions can be bound to other keys.
+
+use bevy::{
+    color::palettes::tailwind, ecs::spawn::SpawnWith, input::InputSystem, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    input::{Action, InputMap, action_just_pressed, key_label, keys_label},
+    menus::Menu,
+    theme::{
+        palette::{
+            BUTTON_BACKGROUND, BUTTON_HOVERED_BACKGROUND, BUTTON_PRESSED_BACKGROUND, BUTTON_TEXT,
+        },
+        prelude::*,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ListeningFor>();
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+    app.add_systems(OnExit(Menu::Controls), stop_listening);
+    // Runs before everything else reads input, so the captured key does nothing else
+    app.add_systems(
+        PreUpdate,
+        capture_binding
+            .after(InputSystem)
+            .run_if(in_state(Menu::Controls)),
+    );
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(action_just_pressed(Action::Back)),
+            update_binding_labels
+                .run_if(resource_changed::<InputMap>.or(resource_changed::<ListeningFor>)),
+        )
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+// The action waiting for its next key press
+#[derive(Resource, Debug, Default)]
+struct ListeningFor(Option<Action>);
+
+#[derive(Component)]
+struct BindingButton(Action);
+
+#[derive(Component)]
+struct BindingLabel(Action);
+
+#[derive(Component)]
+struct ControlsStatus;
+
+const DEFAULT_STATUS: &str = "Click a binding, then press the key to use";
+
+fn spawn_controls_menu(mut commands: Commands) {
+    commands.insert_resource(ListeningFor(None));
+
+    let actions: Vec<_> = Action::all()
+        .into_iter()
+        .filter(|w| cfg!(feature = "dev") || !w.is_dev())
+        .collect();
+
+    commands.spawn((
+        widget::ui_root("Controls Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Controls),
+        children![
+            widget::header("Controls"),
+            (widget::ui_font(DEFAULT_STATUS), ControlsStatus),
+            (
+                Name::new("Bindings Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: Px(6.0),
+                    column_gap: Px(12.0),
+                    grid_template_columns: vec![
+                        GridTrack::px(240.0),
+                        GridTrack::px(170.0),
+                        GridTrack::px(240.0),
+                        GridTrack::px(170.0),
+                    ],
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    for action in actions {
+                        parent.spawn((
+                            widget::ui_font(action.name()),
+                            Node {
+                                justify_self: JustifySelf::End,
+                                ..default()
+                            },
+                        ));
+                        parent
+                            .spawn(binding_button(action))
+                            .observe(start_listening);
+                    }
+                })),
+            ),
+            (
+                Name::new("Controls Buttons"),
+                Node {
+                    column_gap: Px(12.0),
+                    ..default()
+                },
+                children![
+                    widget::button_medium("Defaults", reset_bindings),
+                    widget::button_medium("Back", go_back_on_click),
+                ],
+            ),
+        ],
+    ));
+}
+
+fn binding_button(action: Action) -> impl Bundle {
+    (
+        Name::new("Binding Button"),
+        BindingButton(action),
+        Button,
+        Node {
+            height: Px(28.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BorderRadius::all(Px(4.0)),
+        BackgroundColor(BUTTON_BACKGROUND),
+        InteractionPalette {
+            none: BUTTON_BACKGROUND,
+            hovered: BUTTON_HOVERED_BACKGROUND,
+            pressed: BUTTON_PRESSED_BACKGROUND,
+        },
+        children![(
+            widget::ui_font_color("", BUTTON_TEXT),
+            BindingLabel(action),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+fn start_listening(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&BindingButton>,
+    mut listening: ResMut<ListeningFor>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+) {
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return;
+    };
+    listening.0 = Some(button.0);
+    status.0 = format!("Press a key for {}", button.0.name());
+}
+
+fn stop_listening(mut listening: ResMut<ListeningFor>) {
+    listening.0 = None;
+}
+
+fn capture_binding(
+    mut listening: ResMut<ListeningFor>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut input_map: ResMut<InputMap>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+) {
+    let Some(action) = listening.0 else {
+        return;
+    };
+    let Some(key) = keys.get_just_pressed().next().copied() else {
+        return;
+    };
+    keys.clear_just_pressed(key);
+    listening.0 = None;
+
+    // The back key cancels, unless it is what's being rebound
+    if action != Action::Back && input_map.keys(Action::Back).contains(&key) {
+        status.0 = DEFAULT_STATUS.to_string();
+        return;
+    }
+
+    let taken_from = input_map.bind(action, key);
+    status.0 = if taken_from.is_empty() {
+        format!("{} is now bound to {}", action.name(), key_label(&key))
+    } else {
+        let names: Vec<_> = taken_from.iter().map(|w| w.name()).collect();
+        format!("{} was taken from {}", key_label(&key), names.join(", "))
+    };
+}
+
+// Unbound actions and keys shared between actions are shown in red
+fn update_binding_labels(
+    input_map: Res<InputMap>,
+    listening: Res<ListeningFor>,
+    mut labels: Query<(&BindingLabel, &mut Text, &mut TextColor)>,
+) {
+    for (label, mut text, mut color) in labels.iter_mut() {
+        let keys = input_map.keys(label.0);
+        text.0 = if listening.0 == Some(label.0) {
+            "...".to_string()
+        } else {
+            keys_label(keys)
+        };
+        color.0 = if keys.is_empty() || !input_map.conflicts(label.0).is_empty() {
+            tailwind::RED_400.into()
+        } else {
+            BUTTON_TEXT
+        };
+    }
+}
+
+fn reset_bindings(
+    _: Trigger<Pointer<Click>>,
+    mut input_map: ResMut<InputMap>,
+    mut listening: ResMut<ListeningFor>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+) {
+    *input_map = InputMap::default();
+    listening.0 = None;
+    status.0 = "Controls were reset to the defaults".to_string();
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
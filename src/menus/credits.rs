@@ -1,9 +1,8 @@
 //! The credits menu.
 
-use bevy::{
-    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
-};
+use bevy::{ecs::spawn::SpawnIter, prelude::*, ui::Val::*};
 
+use crate::input::{Action, action_just_pressed};
 use crate::prelude::*;
 use crate::{audio::music, menus::Menu, theme::prelude::*};
 
@@ -11,7 +10,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
+        go_back.run_if(in_state(Menu::Credits).and(action_just_pressed(Action::Back))),
     );
 
     app.add_systems(OnEnter(Menu::Credits), start_credits_music);
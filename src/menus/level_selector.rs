@@ -1,24 +1,47 @@
-//! The credits menu.
+//! The level selector menu.
 
 use bevy::{
-    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+    color::palettes::tailwind,
+    ecs::spawn::{SpawnIter, SpawnWith},
+    prelude::*,
+    ui::Val::*,
 };
 
-use crate::data::levels::LevelData;
-use crate::gameplay::level;
-use crate::level::resource::{LevelSelect, UnlockedLevels};
+use crate::data::{Tower, levels::LevelData};
+use crate::input::{Action, Actions, action_just_pressed};
+use crate::level::resource::{Level, LevelSelect, UnlockedLevels};
 use crate::prelude::*;
-use crate::{audio::music, menus::Menu, theme::prelude::*};
+use crate::save_data::SaveData;
+use crate::theme::palette::{
+    BUTTON_BACKGROUND, BUTTON_HOVERED_BACKGROUND, BUTTON_PRESSED_BACKGROUND, BUTTON_TEXT,
+};
+use crate::{menus::Menu, theme::prelude::*};
+
+const COLUMNS: usize = 4;
+const CARD_WIDTH: f32 = 260.0;
+const THUMBNAIL_WIDTH: f32 = 200.0;
+const THUMBNAIL_HEIGHT: f32 = 100.0;
 
 #[derive(Component)]
 struct LevelIndex(usize);
 
+// The card that keyboard and gamepad input acts on
+#[derive(Resource, Debug, Default, PartialEq)]
+struct FocusedLevel(usize);
+
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FocusedLevel>();
     app.add_systems(OnEnter(Menu::LevelSelector), spawn_menu);
     app.add_observer(level_select_observer);
+    app.add_observer(focus_on_hover);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::LevelSelector).and(input_just_pressed(KeyCode::Escape))),
+        (
+            go_back.run_if(action_just_pressed(Action::Back)),
+            navigate_levels,
+            highlight_focused_card.run_if(resource_changed::<FocusedLevel>),
+        )
+            .run_if(in_state(Menu::LevelSelector)),
     );
 }
 
@@ -26,49 +49,204 @@ fn spawn_menu(
     mut commands: Commands,
     level_data: Res<LevelData>,
     unlocked_levels: Res<UnlockedLevels>,
+    level_select: Res<LevelSelect>,
+    save_data: Res<SaveData>,
 ) {
+    let last = level_data.maps.len().saturating_sub(1);
+    commands.insert_resource(FocusedLevel(level_select.0.min(last)));
+
     commands.spawn((
         widget::ui_root("Select a Level"),
         GlobalZIndex(2),
         StateScoped(Menu::LevelSelector),
         children![
-            level_list(level_data, unlocked_levels),
+            level_list(&level_data, &unlocked_levels, &save_data),
             widget::button("Back", go_back_on_click),
         ],
     ));
 }
 
-fn level_list(level_data: Res<LevelData>, unlocked_levels: Res<UnlockedLevels>) -> impl Bundle {
-    let levels = level_data
-        .maps
-        .iter()
-        .enumerate()
-        .map(|(i, _)| {
-            info!(?unlocked_levels, i);
-            if unlocked_levels.0.contains(&i) {
-                (format!("{}", i + 1), i)
-            } else {
-                (format!("{} (locked)", i + 1), i)
-            }
+fn level_list(
+    level_data: &LevelData,
+    unlocked_levels: &UnlockedLevels,
+    save_data: &SaveData,
+) -> impl Bundle {
+    let cards = (0..level_data.maps.len())
+        .map(|i| {
+            level_card(
+                i,
+                level_data,
+                unlocked_levels.0.contains(&i),
+                save_data.best_result(i).map(|w| w.stars),
+            )
         })
         .collect::<Vec<_>>();
 
-    grid(levels)
+    (
+        Name::new("Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(16.0),
+            column_gap: Px(16.0),
+            grid_template_columns: RepeatedGridTrack::px(COLUMNS as u16, CARD_WIDTH),
+            ..default()
+        },
+        Children::spawn(SpawnIter(cards.into_iter())),
+    )
 }
 
-fn grid(content: Vec<(String, usize)>) -> impl Bundle {
+fn level_card(
+    index: usize,
+    level_data: &LevelData,
+    unlocked: bool,
+    stars: Option<u8>,
+) -> impl Bundle {
+    let level = Level::from_str(level_data.maps[index]);
+    let (name, description) = level_data
+        .info
+        .get(index)
+        .map(|w| (w.name, w.description))
+        .unwrap_or_default();
+    let waves = level_data.enemies.get(index).map_or(0, |w| w.len());
+    let setup = level_data.setup(index);
+    let towers = if setup.towers.len() == Tower::all().len() {
+        "Towers: All".to_string()
+    } else {
+        let names: Vec<_> = setup.towers.iter().map(|w| w.name()).collect();
+        format!("Towers: {}", names.join(", "))
+    };
+    let best = match stars {
+        Some(stars) => format!("Best: {stars}/3 stars"),
+        None => "Not completed yet".to_string(),
+    };
+
     (
-        Name::new("Grid"),
+        Name::new("Level Card"),
+        LevelIndex(index),
+        Button,
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Px(4.0),
+            padding: UiRect::all(Px(8.0)),
+            border: UiRect::all(Px(3.0)),
+            ..default()
+        },
+        BorderRadius::all(Px(8.0)),
+        BorderColor(Color::NONE),
+        BackgroundColor(BUTTON_BACKGROUND),
+        InteractionPalette {
+            none: BUTTON_BACKGROUND,
+            hovered: BUTTON_HOVERED_BACKGROUND,
+            pressed: BUTTON_PRESSED_BACKGROUND,
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(thumbnail(&level));
+            parent.spawn((
+                widget::label(format!("{}. {name}", index + 1)),
+                Pickable::IGNORE,
+            ));
+            parent.spawn((
+                widget::body_text(description),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Pickable::IGNORE,
+            ));
+            parent.spawn((
+                widget::ui_font_color(format!("{waves} waves"), BUTTON_TEXT),
+                Pickable::IGNORE,
+            ));
+            parent.spawn((
+                widget::ui_font_color(towers, BUTTON_TEXT),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Pickable::IGNORE,
+            ));
+            parent.spawn((widget::ui_font_color(best, BUTTON_TEXT), Pickable::IGNORE));
+            if !unlocked {
+                parent.spawn(lock_overlay(index));
+            }
+        })),
+    )
+}
+
+// Levels unlock by beating the one before them
+fn lock_overlay(index: usize) -> impl Bundle {
+    let hint = match index {
+        0 => "Locked".to_string(),
+        i => format!("Complete level {i} to unlock"),
+    };
+    (
+        Name::new("Lock Overlay"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Percent(100.0),
+            height: Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Px(8.0),
+            ..default()
+        },
+        BorderRadius::all(Px(8.0)),
+        BackgroundColor(Color::BLACK.with_alpha(0.75)),
+        Pickable::IGNORE,
+        children![
+            widget::header("Locked"),
+            widget::ui_font_color(hint, BUTTON_TEXT),
+        ],
+    )
+}
+
+// Draws the maze from its walls and floors, rows go top to bottom in the UI
+fn thumbnail(level: &Level) -> impl Bundle {
+    let cell = (THUMBNAIL_WIDTH / level.width as f32)
+        .min(THUMBNAIL_HEIGHT / level.height as f32)
+        .floor();
+    let wall = |present: bool| if present { Px(1.0) } else { Px(0.0) };
+    // The path alternates cell centres and the half steps between them
+    let exit = level.path.iter().rev().nth(1).map(|(w, _)| *w);
+
+    let cells = (0..level.height)
+        .rev()
+        .flat_map(|y| (0..level.width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let position = Vec2::new(x as f32, y as f32);
+            let color = if position == Vec2::ZERO {
+                tailwind::GREEN_500
+            } else if Some(position) == exit {
+                tailwind::RED_500
+            } else if level.path.iter().any(|(w, _)| *w == position) {
+                tailwind::SLATE_500
+            } else {
+                tailwind::SLATE_800
+            };
+            (
+                Node {
+                    border: UiRect {
+                        left: wall(level.walls[x][y]),
+                        right: wall(level.walls[x + 1][y]),
+                        top: wall(level.floors[x][y + 1]),
+                        bottom: wall(level.floors[x][y]),
+                    },
+                    ..default()
+                },
+                BorderColor(tailwind::SLATE_100.into()),
+                BackgroundColor(color.into()),
+                Pickable::IGNORE,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new("Map Thumbnail"),
         Node {
             display: Display::Grid,
-            row_gap: Px(10.0),
-            column_gap: Px(30.0),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            grid_template_columns: RepeatedGridTrack::px(level.width as u16, cell),
+            grid_template_rows: RepeatedGridTrack::px(level.height as u16, cell),
+            margin: UiRect::bottom(Px(4.0)),
             ..default()
         },
-        Children::spawn(SpawnIter(content.into_iter().map(|(text, idx)| {
-            (widget::global_observer_button(text, (LevelIndex(idx))),)
-        }))),
+        Pickable::IGNORE,
+        Children::spawn(SpawnIter(cells.into_iter())),
     )
 }
 
@@ -80,17 +258,90 @@ fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Main);
 }
 
+fn start_level(
+    index: usize,
+    unlocked_levels: &UnlockedLevels,
+    level_select: &mut LevelSelect,
+    next_screen: &mut NextState<Screen>,
+) {
+    if unlocked_levels.0.contains(&index) {
+        level_select.0 = index;
+        next_screen.set(Screen::LevelTransition);
+    }
+}
+
 fn level_select_observer(
     trigger: Trigger<Pointer<Released>>,
     mut level_select: ResMut<LevelSelect>,
     unlocked_levels: Res<UnlockedLevels>,
     level_index: Query<&LevelIndex>,
-    mut next_sceeen: ResMut<NextState<Screen>>,
+    mut next_screen: ResMut<NextState<Screen>>,
 ) {
-    if let Ok(level_index) = level_index.get(trigger.target) {
-        if unlocked_levels.0.contains(&level_index.0) {
-            level_select.0 = level_index.0;
-            next_sceeen.set(Screen::LevelTransition);
-        }
+    if let Ok(level_index) = level_index.get(trigger.target()) {
+        start_level(
+            level_index.0,
+            &unlocked_levels,
+            &mut level_select,
+            &mut next_screen,
+        );
     };
 }
+
+fn focus_on_hover(
+    trigger: Trigger<Pointer<Over>>,
+    level_index: Query<&LevelIndex>,
+    mut focused: ResMut<FocusedLevel>,
+) {
+    if let Ok(level_index) = level_index.get(trigger.target()) {
+        focused.0 = level_index.0;
+    }
+}
+
+fn navigate_levels(
+    actions: Actions,
+    level_data: Res<LevelData>,
+    unlocked_levels: Res<UnlockedLevels>,
+    mut focused: ResMut<FocusedLevel>,
+    mut level_select: ResMut<LevelSelect>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let count = level_data.maps.len();
+    let mut target = focused.0 as isize;
+    if actions.just_pressed(Action::Left) {
+        target -= 1;
+    }
+    if actions.just_pressed(Action::Right) {
+        target += 1;
+    }
+    if actions.just_pressed(Action::Up) {
+        target -= COLUMNS as isize;
+    }
+    if actions.just_pressed(Action::Down) {
+        target += COLUMNS as isize;
+    }
+    if (0..count as isize).contains(&target) {
+        focused.set_if_neq(FocusedLevel(target as usize));
+    }
+
+    if actions.just_pressed(Action::Confirm) {
+        start_level(
+            focused.0,
+            &unlocked_levels,
+            &mut level_select,
+            &mut next_screen,
+        );
+    }
+}
+
+fn highlight_focused_card(
+    focused: Res<FocusedLevel>,
+    mut cards: Query<(&LevelIndex, &mut BorderColor)>,
+) {
+    for (index, mut border) in cards.iter_mut() {
+        border.0 = if index.0 == focused.0 {
+            tailwind::AMBER_400.into()
+        } else {
+            Color::NONE
+        };
+    }
+}
@@ -1,6 +1,12 @@
 //! The main menu (seen on the title screen).
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    gameplay::run_save::{ResumeRun, load_saved_run},
+    level::resource::LevelSelect,
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 use bevy::color::palettes::tailwind;
 use bevy::prelude::*;
 
@@ -11,26 +17,37 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 fn spawn_main_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Main Menu"),
-        GlobalZIndex(2),
-        StateScoped(Menu::Main),
-        #[cfg(not(target_family = "wasm"))]
-        children![
-            widget::title(TITLE_TEXT),
-            widget::button("Play", open_level_selector_menu),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-            widget::button("Exit", exit_app),
-        ],
-        #[cfg(target_family = "wasm")]
-        children![
-            widget::title(TITLE_TEXT),
-            widget::button("Play", open_level_selector_menu),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-        ],
-    ));
+    let menu = commands
+        .spawn((
+            widget::ui_root("Main Menu"),
+            GlobalZIndex(2),
+            StateScoped(Menu::Main),
+            #[cfg(not(target_family = "wasm"))]
+            children![
+                widget::title(TITLE_TEXT),
+                widget::button("Play", open_level_selector_menu),
+                widget::button("Codex", open_codex_menu),
+                widget::button("Settings", open_settings_menu),
+                widget::button("Credits", open_credits_menu),
+                widget::button("Exit", exit_app),
+            ],
+            #[cfg(target_family = "wasm")]
+            children![
+                widget::title(TITLE_TEXT),
+                widget::button("Play", open_level_selector_menu),
+                widget::button("Codex", open_codex_menu),
+                widget::button("Settings", open_settings_menu),
+                widget::button("Credits", open_credits_menu),
+            ],
+        ))
+        .id();
+    // Offer to pick up a level that was left through the pause menu
+    if load_saved_run().is_some() {
+        let continue_button = commands
+            .spawn(widget::button("Continue", continue_saved_run))
+            .id();
+        commands.entity(menu).insert_children(1, &[continue_button]);
+    }
     commands.insert_resource(ClearColor(tailwind::SLATE_950.into()));
     commands.spawn(());
 }
@@ -47,10 +64,28 @@ fn enter_loading_or_gameplay_screen(
     // }
 }
 
+fn continue_saved_run(
+    _: Trigger<Pointer<Click>>,
+    mut level_select: ResMut<LevelSelect>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    let Some(run) = load_saved_run() else {
+        return;
+    };
+    level_select.0 = run.level;
+    commands.insert_resource(ResumeRun(run));
+    next_screen.set(Screen::LevelTransition);
+}
+
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
+fn open_codex_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Codex);
+}
+
 fn open_credits_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Credits);
 }
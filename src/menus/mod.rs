@@ -1,5 +1,7 @@
 //! The game's menus and transitions between them.
 
+mod codex;
+mod controls;
 mod credits;
 mod level_selector;
 mod main;
@@ -17,6 +19,8 @@ pub(super) fn plugin(app: &mut App) {
         settings::plugin,
         pause::plugin,
         level_selector::plugin,
+        codex::plugin,
+        controls::plugin,
     ));
 }
 
@@ -30,4 +34,6 @@ pub enum Menu {
     Settings,
     Pause,
     LevelSelector,
+    Codex,
+    Controls,
 }
@@ -1,14 +1,20 @@
 //! The pause menu.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    gameplay::run_save::SaveRun,
+    input::{Action, action_just_pressed},
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Pause).and(input_just_pressed(KeyCode::Space))),
+        go_back.run_if(in_state(Menu::Pause).and(action_just_pressed(Action::Pause))),
     );
 }
 
@@ -20,6 +26,7 @@ fn spawn_pause_menu(mut commands: Commands) {
         children![
             widget::header("Game paused"),
             widget::button("Continue", close_menu),
+            widget::button("Codex", open_codex_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Quit to title", quit_to_title),
         ],
@@ -30,11 +37,21 @@ fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextStat
     next_menu.set(Menu::Settings);
 }
 
+fn open_codex_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Codex);
+}
+
 fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
 
-fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+// The level is kept so it can be picked up again from the main menu
+fn quit_to_title(
+    _: Trigger<Pointer<Click>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    commands.trigger(SaveRun);
     next_screen.set(Screen::Title);
 }
 
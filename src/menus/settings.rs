@@ -2,18 +2,27 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{audio::Volume, prelude::*, ui::Val::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    gameplay::blueprints::Blueprints,
+    input::{Action, action_just_pressed},
+    level::resource::{LevelSelect, UnlockedLevels},
+    menus::Menu,
+    save_data::{SaveData, reset_progress},
+    screens::{DisplayedControls, Screen},
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        go_back.run_if(in_state(Menu::Settings).and(action_just_pressed(Action::Back))),
     );
 
     app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<ResetProgressLabel>();
     app.add_systems(
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
@@ -52,6 +61,40 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Controls"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            (
+                Name::new("Controls Widget"),
+                Node {
+                    justify_self: JustifySelf::Start,
+                    ..default()
+                },
+                children![widget::button_medium("Rebind keys", open_controls_menu)],
+            ),
+            (
+                widget::label("Progress"),
+                ResetProgressLabel::default(),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            (
+                Name::new("Reset Progress Widget"),
+                Node {
+                    justify_self: JustifySelf::Start,
+                    ..default()
+                },
+                children![widget::button_medium(
+                    "Reset progress",
+                    reset_progress_on_click
+                )],
+            ),
         ],
     )
 }
@@ -104,6 +147,43 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn open_controls_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
+}
+
+// The first click only asks, the second one wipes the progress
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct ResetProgressLabel {
+    confirming: bool,
+}
+
+fn reset_progress_on_click(
+    _: Trigger<Pointer<Click>>,
+    mut save_data: ResMut<SaveData>,
+    mut unlocked_levels: ResMut<UnlockedLevels>,
+    mut level_select: ResMut<LevelSelect>,
+    mut displayed_controls: ResMut<DisplayedControls>,
+    mut blueprints: ResMut<Blueprints>,
+    mut label: Single<(&mut Text, &mut ResetProgressLabel)>,
+) {
+    let (text, reset_label) = &mut *label;
+    if !reset_label.confirming {
+        reset_label.confirming = true;
+        text.0 = "Click again to erase all progress".to_string();
+        return;
+    }
+    reset_progress(
+        &mut save_data,
+        &mut unlocked_levels,
+        &mut level_select,
+        &mut displayed_controls,
+        &mut blueprints,
+    );
+    reset_label.confirming = false;
+    text.0 = "Progress was reset".to_string();
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,116 @@
+//! Small key-value storage for things that outlive a session. Values are stored as RON,
+//! in the platform data dir on native builds and in `localStorage` on the web. Exports are
+//! written as-is next to them, for reading outside the game.
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+const APP_NAME: &str = "tower_combinator";
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let text = match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(text) => text,
+        Err(err) => {
+            warn!("Could not serialize {key}: {err}");
+            return;
+        }
+    };
+    if let Err(err) = write(key, &text) {
+        warn!("Could not save {key}: {err}");
+    }
+}
+
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let text = read(key)?;
+    match ron::from_str(&text) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            warn!("Could not parse saved {key}: {err}");
+            None
+        }
+    }
+}
+
+pub fn remove(key: &str) {
+    if let Err(err) = delete(key) {
+        warn!("Could not remove {key}: {err}");
+    }
+}
+
+// Writes `text` under `file_name`, returning where it ended up
+#[cfg(not(target_family = "wasm"))]
+pub fn export(file_name: &str, text: &str) -> Result<String, String> {
+    let dir = dirs::data_dir().ok_or("no data directory")?;
+    let path = dir.join(APP_NAME).join("exports").join(file_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|w| w.to_string())?;
+    }
+    std::fs::write(&path, text).map_err(|w| w.to_string())?;
+    Ok(path.display().to_string())
+}
+
+#[cfg(target_family = "wasm")]
+pub fn export(file_name: &str, text: &str) -> Result<String, String> {
+    let key = format!("{APP_NAME}.exports.{file_name}");
+    storage()?
+        .set_item(&key, text)
+        .map_err(|w| format!("{w:?}"))?;
+    Ok(format!("localStorage key {key}"))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn path(key: &str) -> Option<std::path::PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_NAME).join(format!("{key}.ron")))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write(key: &str, text: &str) -> Result<(), String> {
+    let path = path(key).ok_or("no data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|w| w.to_string())?;
+    }
+    std::fs::write(path, text).map_err(|w| w.to_string())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(path(key)?).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn delete(key: &str) -> Result<(), String> {
+    match path(key) {
+        Some(path) if path.exists() => std::fs::remove_file(path).map_err(|w| w.to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| "localStorage is unavailable".to_string())
+}
+
+#[cfg(target_family = "wasm")]
+fn write(key: &str, text: &str) -> Result<(), String> {
+    storage()?
+        .set_item(&format!("{APP_NAME}.{key}"), text)
+        .map_err(|w| format!("{w:?}"))
+}
+
+#[cfg(target_family = "wasm")]
+fn read(key: &str) -> Option<String> {
+    storage()
+        .ok()?
+        .get_item(&format!("{APP_NAME}.{key}"))
+        .ok()
+        .flatten()
+}
+
+#[cfg(target_family = "wasm")]
+fn delete(key: &str) -> Result<(), String> {
+    storage()?
+        .remove_item(&format!("{APP_NAME}.{key}"))
+        .map_err(|w| format!("{w:?}"))
+}
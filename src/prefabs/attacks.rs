@@ -1,13 +1,13 @@
 use avian2d::prelude::{
     Collider, CollisionEventsEnabled, CollisionLayers, LinearVelocity, Mass, RigidBody, Sensor,
 };
-use bevy::{ecs::system::Res, math::Vec2, render::view::Visibility};
+use bevy::{color::Color, ecs::system::Res, math::Vec2, render::view::Visibility, sprite::Sprite};
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
 
 use super::physics::GamePhysicsLayer as GPL;
 use super::utils::{DropletSprite, PuddleSprite};
 use crate::assets::LiquidSprites;
-use crate::data::projectiles::{Droplet, LiquidType, Puddle};
+use crate::data::projectiles::{Droplet, LiquidType, Projectile, Puddle};
 use crate::gameplay::shared_systems::Lifetime;
 
 pub fn droplet(liquid: LiquidType) -> ComponentTree {
@@ -41,3 +41,25 @@ pub fn puddle(liquid: LiquidType) -> ComponentTree {
         .store()
         + name("Puddle")
 }
+
+pub fn projectile(
+    projectile: Projectile,
+    velocity: Vec2,
+    color: Color,
+    lifetime: f32,
+) -> ComponentTree {
+    (
+        Sprite::from_color(color, Vec2::splat(2.)),
+        Visibility::Visible,
+        projectile,
+        Collider::circle(1.),
+        CollisionLayers::new(GPL::Projectiles, [GPL::Enemy, GPL::Level]),
+        Sensor,
+        RigidBody::Kinematic,
+        LinearVelocity(velocity),
+        CollisionEventsEnabled,
+        Lifetime::new(lifetime),
+    )
+        .store()
+        + name("Projectile")
+}
@@ -1,34 +1,64 @@
 use super::{
     physics::GamePhysicsLayer as GPL,
-    utils::{color, image, layout, mesh},
+    utils::{color, image, layout, mesh, tint},
 };
 use crate::{
     assets::GameAssets,
     data::stats::{DamageMultiplier, MoveSpeed, Stat, StatFriction},
     demo::{
         enemy_health::{EnemyHealth, EnemyHealthBar},
-        enemy_movement::MovementDirection,
+        enemy_movement::{MovementDirection, PathProgress},
     },
     gameplay::animation::AnimationFrameQueue,
     prelude::*,
 };
 use crate::{
-    data::{projectiles::DamageType, stats::DamageMultiplierAll},
-    demo::enemy_health::Bounty,
+    data::{
+        Tower, projectiles::DamageType, stats::DamageMultiplierAll, status_effects::StatusEnum,
+    },
+    demo::{enemy_health::Bounty, enemy_sabotage::TowerSaboteur},
 };
 use avian2d::prelude::{
     Collider, CollisionLayers, Friction, GravityScale, LinearDamping, LockedAxes, Mass, RigidBody,
 };
+use bevy::color::palettes::css::{AQUA, DODGER_BLUE, YELLOW};
 use bevy::prelude::*;
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
+use serde::{Deserialize, Serialize};
 use std::default::Default;
 
 #[derive(Component, Reflect, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct EnemySprite;
 
+// Which prefab an enemy came from, so it can be spawned again from a save
+#[derive(Component, Reflect, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum EnemyKind {
+    Basic,
+    Chonkus,
+    Turbo,
+    Saboteur,
+    Emp,
+    Soaker,
+}
+
+impl EnemyKind {
+    pub fn prefab(&self) -> ComponentTree {
+        match self {
+            EnemyKind::Basic => basic_trooper(),
+            EnemyKind::Chonkus => chonkus_trooper(),
+            EnemyKind::Turbo => turbo_trooper(),
+            EnemyKind::Saboteur => saboteur_trooper(),
+            EnemyKind::Emp => emp_trooper(),
+            EnemyKind::Soaker => soaker_trooper(),
+        }
+    }
+}
+
 pub fn basic_trooper() -> ComponentTree {
     let animation = AnimationFrameQueue::new(&[8, 9, 10, 11, 12, 13, 14]);
-    name("Minor Trooper") + enemy_requirements(Vec2::new(3., 4.), 30., 10)
+    name("Minor Trooper")
+        + EnemyKind::Basic.store()
+        + enemy_requirements(Vec2::new(3., 4.), 30., 10)
         << ((
             Transform::from_translation(Vec3::new(0., 0.5, 0.)),
             Pickable::default(),
@@ -44,6 +74,7 @@ pub fn basic_trooper() -> ComponentTree {
 pub fn chonkus_trooper() -> ComponentTree {
     let animation = AnimationFrameQueue::new(&[16, 16, 16, 17, 17, 17, 18, 18, 18, 19, 19, 19]);
     name("Major Trooper")
+        + EnemyKind::Chonkus.store()
         + enemy_requirements(Vec2::new(4., 5.0), 20., 20)
         + Stat::<DamageMultiplierAll>::new(0.75).store()
         << ((
@@ -61,6 +92,7 @@ pub fn chonkus_trooper() -> ComponentTree {
 pub fn turbo_trooper() -> ComponentTree {
     let animation = AnimationFrameQueue::new(&[0, 1, 2, 3, 4, 5, 6, 7]);
     name("Turbo Trooper")
+        + EnemyKind::Turbo.store()
         + enemy_requirements(Vec2::new(2., 3.), 45., 15)
         + Stat::<DamageMultiplierAll>::new(1.15).store()
         << ((
@@ -75,6 +107,65 @@ pub fn turbo_trooper() -> ComponentTree {
             << health_bar(2.5))
 }
 
+// Freezes whatever tower it walks past
+pub fn saboteur_trooper() -> ComponentTree {
+    let animation = AnimationFrameQueue::new(&[8, 9, 10, 11, 12, 13, 14]);
+    name("Saboteur")
+        + EnemyKind::Saboteur.store()
+        + enemy_requirements(Vec2::new(3., 4.), 30., 20)
+        + TowerSaboteur::new(StatusEnum::Frozen, 1, 20., 5.).store()
+        << ((
+            Transform::from_translation(Vec3::new(0., 0.5, 0.)),
+            Pickable::default(),
+            EnemySprite,
+        )
+            .store()
+            + animation.store()
+            + tint(AQUA.into())
+            + image(GameAssets::troopers, 6.0)
+            + layout(GameAssets::troopers_layout)
+            << health_bar(3.))
+}
+
+// Shorts out Tesla towers
+pub fn emp_trooper() -> ComponentTree {
+    let animation = AnimationFrameQueue::new(&[0, 1, 2, 3, 4, 5, 6, 7]);
+    name("EMP Trooper")
+        + EnemyKind::Emp.store()
+        + enemy_requirements(Vec2::new(2., 3.), 40., 20)
+        + TowerSaboteur::new(StatusEnum::Electrocuted, 3, 25., 3.)
+            .targeting(Tower::Tesla)
+            .store()
+        << ((Pickable::default(), EnemySprite).store()
+            + animation.store()
+            + tint(YELLOW.into())
+            + image(GameAssets::troopers, 5.0)
+            + layout(GameAssets::troopers_layout)
+            << health_bar(2.5))
+}
+
+// Douses Flame towers so they fire slower
+pub fn soaker_trooper() -> ComponentTree {
+    let animation = AnimationFrameQueue::new(&[16, 16, 16, 17, 17, 17, 18, 18, 18, 19, 19, 19]);
+    name("Soaker")
+        + EnemyKind::Soaker.store()
+        + enemy_requirements(Vec2::new(4., 5.0), 20., 25)
+        + TowerSaboteur::new(StatusEnum::Wet, 2, 20., 4.)
+            .targeting(Tower::Flame)
+            .store()
+        << ((
+            Transform::from_translation(Vec3::new(0., 1., 0.)),
+            Pickable::default(),
+            EnemySprite,
+        )
+            .store()
+            + animation.store()
+            + tint(DODGER_BLUE.into())
+            + image(GameAssets::troopers, 8.0)
+            + layout(GameAssets::troopers_layout)
+            << health_bar(4.))
+}
+
 pub fn enemy_requirements(size: Vec2, speed: f32, bounty: i32) -> ComponentTree {
     (
         StateScoped(Screen::Gameplay),
@@ -94,6 +185,7 @@ pub fn enemy_requirements(size: Vec2, speed: f32, bounty: i32) -> ComponentTree
     )
         .store()
         + (
+            PathProgress::default(),
             Stat::<MoveSpeed>::new(speed),
             Stat::<StatFriction>::new(0.3),
             Stat::<DamageMultiplierAll>::new(1.0),
@@ -3,7 +3,10 @@ use bevy::math::Vec2;
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
 
 use crate::{
-    data::Tower,
+    data::{
+        Tower,
+        stats::{AttackDamage, AttackStrength, FireRate, PushForce, Stat, TriggerRange},
+    },
     gameplay::towers::{
         common::{TowerTriggerNeedsGravity, TowerTriggerRange},
         directional::FireDirection,
@@ -16,12 +19,15 @@ use super::{physics::GamePhysicsLayer as GPL, utils::TowerSprite};
 
 pub fn tower(tower: Tower, direction: CellDirection) -> ComponentTree {
     let tower_specific_components = match tower {
-        Tower::Piston => FireDirection(direction.flip()).store(),
-        Tower::Fan => FanNeedsDirection.store(),
+        Tower::Piston | Tower::Harpoon | Tower::FrostCannon | Tower::AcidSprayer => {
+            FireDirection(direction.flip()).store()
+        }
+        Tower::Fan => FanNeedsDirection(None).store(),
         Tower::Portal => todo!(),
         _ => ().store(),
     };
     (tower, direction, TowerSprite(tower, direction)).store()
+        + tower_stats()
         + name(tower.name())
         + tower_specific_components
         + {
@@ -40,6 +46,18 @@ pub fn tower(tower: Tower, direction: CellDirection) -> ComponentTree {
         }
 }
 
+// Tower stats are multipliers on the values in `data::towers`, except strength
+pub fn tower_stats() -> ComponentTree {
+    (
+        Stat::<FireRate>::new(1.0),
+        Stat::<AttackDamage>::new(1.0),
+        Stat::<PushForce>::new(1.0),
+        Stat::<TriggerRange>::new(1.0),
+        Stat::<AttackStrength>::new(1.0),
+    )
+        .store()
+}
+
 pub fn trigger_zone(size: Vec2) -> ComponentTree {
     (
         CollisionEventsEnabled,
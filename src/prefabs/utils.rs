@@ -5,6 +5,7 @@ use crate::{
     data::{Tower, projectiles::LiquidType},
     level::resource::CellDirection,
 };
+use bevy::color::Color;
 use bevy::math::Vec2;
 use bevy::{
     app::{Plugin, Update},
@@ -27,6 +28,10 @@ use std::sync::Arc;
 #[derive(Component, Clone)]
 pub struct GiveMeImage(pub Arc<dyn GimmieFn<Image, GameAssets>>, pub Option<f32>);
 
+// Colors the sprite given by `GiveMeImage`
+#[derive(Component, Clone)]
+pub struct Tint(pub Color);
+
 #[derive(Component, Clone)]
 pub struct GiveMeLayout(pub Arc<dyn GimmieFn<TextureAtlasLayout, GameAssets>>);
 
@@ -53,6 +58,10 @@ pub fn layout(layout: impl GimmieFn<TextureAtlasLayout, GameAssets>) -> Componen
     GiveMeLayout(Arc::new(layout)).store()
 }
 
+pub fn tint(color: Color) -> ComponentTree {
+    Tint(color).store()
+}
+
 pub fn mesh(mesh: impl GimmieFn<Mesh, GameAssets>) -> ComponentTree {
     GiveMeMesh(Arc::new(mesh)).store()
 }
@@ -84,17 +93,18 @@ pub fn show_delay(mut query: Query<(&mut Visibility, &mut ShowDelay)>, time: Res
 pub fn give_images(
     trigger: Trigger<OnAdd, GiveMeImage>,
     images: Res<GameAssets>,
-    requests: Query<&GiveMeImage>,
+    requests: Query<(&GiveMeImage, Option<&Tint>)>,
     mut commands: Commands,
 ) {
     let entity = trigger.target();
-    let request = requests.get(entity).unwrap();
+    let (request, tint) = requests.get(entity).unwrap();
     commands
         .get_entity(entity)
         .unwrap()
         .insert(Sprite {
             custom_size: request.1.map(|s| Vec2::splat(s)),
             image: request.0(&images),
+            color: tint.map(|w| w.0).unwrap_or(Color::WHITE),
             ..Default::default()
         })
         .remove::<GiveMeImage>();
@@ -0,0 +1,224 @@
+//! Campaign progress, settings and tutorial flags that are kept between sessions.
+
+use std::collections::HashMap;
+
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::status_effects::Reaction,
+    gameplay::{
+        blueprints::Blueprints, run_save::discard_saved_run,
+        status_effects::common::ReactionTriggered,
+    },
+    input::InputMap,
+    level::{
+        START_LEVEL,
+        resource::{LevelCompleted, LevelSelect, UnlockedLevels},
+    },
+    persistence,
+    screens::DisplayedControls,
+};
+
+const SAVE_KEY: &str = "save";
+// Bump when the format changes and teach `SaveData::migrate` about the old one
+const SAVE_VERSION: u32 = 1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SaveData>();
+    app.add_systems(Startup, load_save_data);
+    app.add_systems(
+        Update,
+        (
+            sync_save_data.run_if(
+                resource_changed::<UnlockedLevels>
+                    .or(resource_changed::<LevelSelect>)
+                    .or(resource_changed::<GlobalVolume>)
+                    .or(resource_changed::<InputMap>)
+                    .or(resource_changed::<DisplayedControls>),
+            ),
+            record_discoveries.run_if(on_event::<ReactionTriggered>),
+            write_save_data.run_if(resource_changed::<SaveData>),
+        )
+            .chain(),
+    );
+    app.add_observer(record_level_result);
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LevelResult {
+    pub lives: i32,
+    #[serde(default)]
+    pub stars: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub unlocked_levels: Vec<usize>,
+    pub last_level: usize,
+    pub best_results: HashMap<usize, LevelResult>,
+    // Reactions the player has set off at least once, in discovery order
+    #[serde(default)]
+    pub discovered_reactions: Vec<Reaction>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self {
+            unlocked_levels: vec![START_LEVEL],
+            last_level: START_LEVEL,
+            best_results: HashMap::new(),
+            discovered_reactions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub volume: f32,
+    #[serde(default)]
+    pub controls: InputMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            controls: default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TutorialFlags {
+    pub controls_shown: bool,
+    // Levels whose tutorial was finished or skipped
+    #[serde(default)]
+    pub completed_levels: Vec<usize>,
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SaveData {
+    pub version: u32,
+    pub progress: Progress,
+    pub settings: Settings,
+    pub tutorials: TutorialFlags,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            progress: default(),
+            settings: default(),
+            tutorials: default(),
+        }
+    }
+}
+
+impl SaveData {
+    // Saves from a newer build are left alone rather than guessed at
+    fn migrate(self) -> Option<Self> {
+        match self.version {
+            SAVE_VERSION => Some(self),
+            version => {
+                warn!("Ignoring save data with unknown version {version}");
+                None
+            }
+        }
+    }
+
+    pub fn best_result(&self, level: usize) -> Option<LevelResult> {
+        self.progress.best_results.get(&level).copied()
+    }
+
+    pub fn discovered(&self, reaction: Reaction) -> bool {
+        self.progress.discovered_reactions.contains(&reaction)
+    }
+}
+
+fn load_save_data(
+    mut save_data: ResMut<SaveData>,
+    mut unlocked_levels: ResMut<UnlockedLevels>,
+    mut level_select: ResMut<LevelSelect>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut input_map: ResMut<InputMap>,
+    mut displayed_controls: ResMut<DisplayedControls>,
+) {
+    let Some(loaded) = persistence::load::<SaveData>(SAVE_KEY).and_then(SaveData::migrate) else {
+        return;
+    };
+
+    unlocked_levels.0 = loaded.progress.unlocked_levels.clone();
+    level_select.0 = loaded.progress.last_level;
+    global_volume.volume = Volume::Linear(loaded.settings.volume);
+    *input_map = loaded.settings.controls.clone();
+    displayed_controls.0 = loaded.tutorials.controls_shown;
+    *save_data = loaded;
+}
+
+fn sync_save_data(
+    mut save_data: ResMut<SaveData>,
+    unlocked_levels: Res<UnlockedLevels>,
+    level_select: Res<LevelSelect>,
+    global_volume: Res<GlobalVolume>,
+    input_map: Res<InputMap>,
+    displayed_controls: Res<DisplayedControls>,
+) {
+    let mut updated = save_data.clone();
+    updated.progress.unlocked_levels = unlocked_levels.0.clone();
+    updated.progress.last_level = level_select.0;
+    updated.settings.volume = global_volume.volume.to_linear();
+    updated.settings.controls = input_map.clone();
+    updated.tutorials.controls_shown = displayed_controls.0;
+    save_data.set_if_neq(updated);
+}
+
+fn write_save_data(save_data: Res<SaveData>) {
+    persistence::save(SAVE_KEY, &*save_data);
+}
+
+fn record_level_result(trigger: Trigger<LevelCompleted>, mut save_data: ResMut<SaveData>) {
+    let result = match save_data.best_result(trigger.level) {
+        Some(best) => LevelResult {
+            lives: best.lives.max(trigger.lives),
+            stars: best.stars.max(trigger.stars),
+        },
+        None => LevelResult {
+            lives: trigger.lives,
+            stars: trigger.stars,
+        },
+    };
+    save_data
+        .progress
+        .best_results
+        .insert(trigger.level, result);
+}
+
+fn record_discoveries(
+    mut reactions: EventReader<ReactionTriggered>,
+    mut save_data: ResMut<SaveData>,
+) {
+    for event in reactions.read() {
+        if !save_data.discovered(event.reaction) {
+            save_data.progress.discovered_reactions.push(event.reaction);
+        }
+    }
+}
+
+// Forgets campaign progress, tutorials, the saved run and layouts, settings are kept
+pub fn reset_progress(
+    save_data: &mut SaveData,
+    unlocked_levels: &mut UnlockedLevels,
+    level_select: &mut LevelSelect,
+    displayed_controls: &mut DisplayedControls,
+    blueprints: &mut Blueprints,
+) {
+    save_data.progress = Progress::default();
+    save_data.tutorials = TutorialFlags::default();
+    unlocked_levels.0 = save_data.progress.unlocked_levels.clone();
+    level_select.0 = save_data.progress.last_level;
+    displayed_controls.0 = save_data.tutorials.controls_shown;
+    blueprints.clear();
+    discard_saved_run();
+}
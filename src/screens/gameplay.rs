@@ -1,8 +1,10 @@
 //! The screen state for the main gameplay.
 
-use crate::data::{PlayerState, PointerInteractionState};
+use crate::data::{PointerInteractionState, levels::LevelData};
+use crate::input::{Action, action_just_pressed};
+use crate::level::resource::LevelSelect;
 use crate::{Pause, gameplay::level::spawn_level, menus::Menu, screens::Screen};
-use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{prelude::*, ui::Val::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
@@ -16,16 +18,12 @@ pub(super) fn plugin(app: &mut App) {
                 in_state(Screen::Gameplay)
                     .and(in_state(Menu::None))
                     .and(in_state(PointerInteractionState::Selecting))
-                    .and(
-                        input_just_pressed(KeyCode::Space).or(input_just_pressed(KeyCode::Escape)),
-                    ),
+                    .and(action_just_pressed(Action::Pause).or(action_just_pressed(Action::Back))),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
-                    .and(
-                        input_just_pressed(KeyCode::Space).or(input_just_pressed(KeyCode::Escape)),
-                    ),
+                    .and(action_just_pressed(Action::Pause).or(action_just_pressed(Action::Back))),
             ),
         ),
     );
@@ -45,8 +43,12 @@ fn pause(mut next_pause: ResMut<NextState<Pause>>) {
     next_pause.set(Pause(true));
 }
 
-fn on_game_start(mut commands: Commands) {
-    commands.insert_resource(PlayerState::default());
+fn on_game_start(
+    mut commands: Commands,
+    level_data: Res<LevelData>,
+    level_select: Res<LevelSelect>,
+) {
+    commands.insert_resource(level_data.setup(level_select.0).player_state());
 }
 
 fn spawn_pause_overlay(mut commands: Commands) {
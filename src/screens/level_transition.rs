@@ -2,12 +2,13 @@
 
 use crate::assets::UiAssets;
 use crate::data::levels::LevelData;
+use crate::input::{Action, action_just_pressed};
 use crate::level::resource::{CurrentLoadedLevel, LevelSelect};
 use crate::{AppSystems, screens::Screen, theme::prelude::*};
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 #[derive(Resource)]
-struct DisplayedControls(bool);
+pub(crate) struct DisplayedControls(pub(crate) bool);
 
 pub(super) fn plugin(app: &mut App) {
     // Spawn splash screen.
@@ -33,7 +34,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         enter_title_screen
-            .run_if(input_just_pressed(KeyCode::Escape).and(in_state(Screen::LevelTransition))),
+            .run_if(action_just_pressed(Action::Back).and(in_state(Screen::LevelTransition))),
     );
 
     app.add_systems(
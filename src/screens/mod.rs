@@ -5,6 +5,9 @@ mod level_transition;
 mod loading;
 mod splash;
 mod title;
+mod victory;
+
+pub(crate) use level_transition::DisplayedControls;
 
 use bevy::prelude::*;
 
@@ -17,6 +20,7 @@ pub(super) fn plugin(app: &mut App) {
         splash::plugin,
         title::plugin,
         level_transition::plugin,
+        victory::plugin,
     ));
 }
 
@@ -30,4 +34,5 @@ pub enum Screen {
     Title,
     Gameplay,
     LevelTransition,
+    Victory,
 }
@@ -1,6 +1,7 @@
 //! A splash screen that plays briefly at startup.
 
 use crate::assets::UiAssets;
+use crate::input::{Action, action_just_pressed};
 use crate::{AppSystems, screens::Screen, theme::prelude::*};
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
@@ -35,8 +36,7 @@ pub(super) fn plugin(app: &mut App) {
     // Exit the splash screen early if the player hits escape.
     app.add_systems(
         Update,
-        enter_title_screen
-            .run_if(input_just_pressed(KeyCode::Escape).and(in_state(Screen::Splash))),
+        enter_title_screen.run_if(action_just_pressed(Action::Back).and(in_state(Screen::Splash))),
     );
     app.add_systems(
         Update,
@@ -3,6 +3,7 @@
 use bevy::prelude::*;
 
 use crate::{
+    data::levels::LevelData,
     level::{
         START_LEVEL,
         resource::{LevelSelect, UnlockedLevels},
@@ -16,8 +17,15 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnExit(Screen::Title), close_menu);
 }
 
-fn open_main_menu(mut next_menu: ResMut<NextState<Menu>>, mut level_select: ResMut<LevelSelect>) {
-    level_select.0 = START_LEVEL;
+fn open_main_menu(
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut level_select: ResMut<LevelSelect>,
+    level_data: Res<LevelData>,
+) {
+    // Keep the last played level selected unless the campaign was finished
+    if level_select.0 >= level_data.maps.len() {
+        level_select.0 = START_LEVEL;
+    }
     next_menu.set(Menu::Main);
 }
 
@@ -0,0 +1,139 @@
+//! The screen shown after the last wave of a level has been cleared.
+
+use bevy::{
+    color::palettes::tailwind,
+    ecs::spawn::{SpawnIter, SpawnWith},
+    prelude::*,
+    ui::Val::*,
+};
+
+use crate::{
+    data::levels::LevelData, gameplay::level_stats::LevelSummary, level::resource::LevelSelect,
+    screens::Screen, theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Victory), spawn_victory_screen);
+}
+
+fn spawn_victory_screen(
+    mut commands: Commands,
+    summary: Option<Res<LevelSummary>>,
+    level_data: Res<LevelData>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let Some(summary) = summary else {
+        next_screen.set(Screen::Title);
+        return;
+    };
+
+    let mut lines = vec![
+        format!("Lives remaining: {} / {}", summary.lives, summary.max_lives),
+        format!("Money spent: {}", summary.money_spent),
+    ];
+    if summary.kills.is_empty() {
+        lines.push("No kills".to_string());
+    } else {
+        lines.push("Kills:".to_string());
+        lines.extend(
+            summary
+                .kills
+                .iter()
+                .map(|(name, count)| format!("  {name}: {count}")),
+        );
+    }
+    if !summary.top_reactions.is_empty() {
+        lines.push("Top reactions:".to_string());
+        lines.extend(
+            summary
+                .top_reactions
+                .iter()
+                .map(|(reaction, count)| format!("  {}: {count}", reaction.name())),
+        );
+    }
+
+    // There is nothing to move on to after the last level
+    let campaign_complete = summary.level + 1 >= level_data.maps.len();
+    let header = if campaign_complete {
+        "Campaign complete!".to_string()
+    } else {
+        format!("Level {} complete!", summary.level + 1)
+    };
+
+    commands.spawn((
+        widget::ui_root("Victory Screen"),
+        BackgroundColor(tailwind::SLATE_950.into()),
+        StateScoped(Screen::Victory),
+        children![
+            widget::header(header),
+            star_rating(summary.stars),
+            (
+                Name::new("Level Stats"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(4.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(lines.into_iter().map(widget::label))),
+            ),
+            (
+                Name::new("Victory Buttons"),
+                Node {
+                    column_gap: Px(20.0),
+                    ..default()
+                },
+                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                    parent.spawn(widget::button("Replay", replay_level));
+                    if !campaign_complete {
+                        parent.spawn(widget::button("Next Level", next_level));
+                    }
+                    parent.spawn(widget::button("Main Menu", main_menu));
+                })),
+            ),
+        ],
+    ));
+}
+
+fn star_rating(stars: u8) -> impl Bundle {
+    (
+        Name::new("Star Rating"),
+        Node {
+            column_gap: Px(12.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter((0..3).map(move |i| {
+            let color = if i < stars {
+                tailwind::AMBER_400
+            } else {
+                tailwind::SLATE_600
+            };
+            (
+                Name::new("Star"),
+                Node {
+                    width: Px(36.0),
+                    height: Px(36.0),
+                    ..default()
+                },
+                BorderRadius::all(Px(18.0)),
+                BackgroundColor(color.into()),
+            )
+        }))),
+    )
+}
+
+fn replay_level(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::LevelTransition);
+}
+
+fn next_level(
+    _: Trigger<Pointer<Click>>,
+    mut level_select: ResMut<LevelSelect>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    level_select.0 += 1;
+    next_screen.set(Screen::LevelTransition);
+}
+
+fn main_menu(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}